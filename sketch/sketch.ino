@@ -5,6 +5,20 @@ const int RECV_PIN = 2;  // IR-remote TSOP4838
 IRrecv irrecv(RECV_PIN);
 decode_results results;
 
+const char* protocolName(decode_type_t type) {
+  switch (type) {
+    case NEC: return "NEC";
+    case SONY: return "SONY";
+    case RC5: return "RC5";
+    case RC6: return "RC6";
+    case SAMSUNG: return "SAMSUNG";
+    case LG: return "LG";
+    case JVC: return "JVC";
+    case PANASONIC: return "PANASONIC";
+    default: return "UNKNOWN";
+  }
+}
+
 void setup() {
   Serial.begin(9600);
   irrecv.enableIRIn();
@@ -12,7 +26,9 @@ void setup() {
 
 void loop() {
   if (irrecv.decode(&results)) {
+    Serial.print(protocolName(results.decode_type));
+    Serial.print(' ');
     Serial.println(results.value, HEX);
     irrecv.resume();
   }
-}
\ No newline at end of file
+}
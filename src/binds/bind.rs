@@ -1,19 +1,143 @@
 use crate::{ prelude::*, Key };
 
 /// The remote bind action
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum Action {
-    Handler { handler: String },
+    Handler {
+        handler: String,
+        #[serde(default, skip_serializing_if = "Value::is_null")]
+        args: Value,
+    },
     Shortcut { shortcut: Vec<Key> },
     Press { press: Vec<Key> },
+    KeyDown { key_down: Vec<Key> },
+    KeyUp { key_up: Vec<Key> },
+    Hold { hold: Vec<Key>, duration_ms: u64 },
     Open { open: String },
+    Script {
+        script: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_ms: Option<u64>,
+    },
+    None,
+}
+
+impl ::std::default::Default for Action {
+    fn default() -> Self {
+        Self::None
+    }
 }
 
 /// The remote bind
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Bind {
     pub name: String,
+    #[serde(default)]
     pub action: Action,
-    pub repeat: bool
+    #[serde(default)]
+    pub repeat: Repeat,
+}
+
+/// The bind repeat option
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Repeat {
+    Flag(bool),
+    Policy(RepeatPolicy),
+}
+
+impl Repeat {
+    /// Checks the bind is repeatable
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            Self::Flag(enabled) => *enabled,
+            Self::Policy(_) => true,
+        }
+    }
+
+    /// Returns repeat policy (if bind is repeatable)
+    pub fn policy(&self) -> Option<RepeatPolicy> {
+        match self {
+            Self::Flag(true) => Some(RepeatPolicy::default()),
+            Self::Flag(false) => None,
+            Self::Policy(policy) => Some(policy.clone()),
+        }
+    }
+}
+
+impl ::std::default::Default for Repeat {
+    fn default() -> Self {
+        Self::Flag(false)
+    }
+}
+
+/// The bind repeat policy
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RepeatPolicy {
+    pub delay_ms: u64,
+    pub interval_ms: u64,
+    pub max_repeats: Option<u32>,
+    pub steps: Vec<i32>,
+}
+
+impl RepeatPolicy {
+    /// Returns initial delay before repeats start
+    pub fn delay(&self) -> Duration {
+        Duration::from_millis(self.delay_ms)
+    }
+
+    /// Returns minimum interval between repeats
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval_ms)
+    }
+
+    /// Checks the repeat limit is reached
+    pub fn is_exhausted(&self, repeats: u32) -> bool {
+        self.max_repeats.map(|max| repeats >= max).unwrap_or(false)
+    }
+}
+
+impl ::std::default::Default for RepeatPolicy {
+    fn default() -> Self {
+        Self {
+            delay_ms: 0,
+            interval_ms: 25,
+            max_repeats: None,
+            steps: vec![],
+        }
+    }
+}
+
+/// The bind trigger info (press or repeat)
+#[derive(Debug, Clone, Default)]
+pub struct Trigger {
+    pub repeats: u32,
+    pub steps: Vec<i32>,
+}
+
+impl Trigger {
+    /// Creates a trigger of bind press/repeat
+    pub fn new(repeats: u32, bind: &Bind) -> Self {
+        Self {
+            repeats,
+            steps: bind.repeat.policy().map(|policy| policy.steps).unwrap_or_default(),
+        }
+    }
+
+    /// Checks the bind is repeated
+    pub fn is_repeated(&self) -> bool {
+        self.repeats > 0
+    }
+
+    /// Returns step size by repeat index (or handler's default (press, repeat) steps)
+    pub fn step(&self, defaults: (i32, i32)) -> i32 {
+        if self.steps.is_empty() {
+            return if self.is_repeated() { defaults.1 }else{ defaults.0 };
+        }
+
+        let index = (self.repeats as usize).min(self.steps.len() - 1);
+        self.steps[index]
+    }
 }
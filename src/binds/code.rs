@@ -0,0 +1,80 @@
+/// The NEC repeat frame code
+pub const REPEAT_CODE: &str = "FFFFFFFF";
+
+/// The received remote code
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Code {
+    pub protocol: Option<String>,
+    pub value: String,
+}
+
+impl Code {
+    /// Parses a receiver line ('FF8877' or 'NEC FF8877')
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut parts = line.split_whitespace();
+
+        let (protocol, value) = match (parts.next(), parts.next()) {
+            (Some(value), None) => (None, value),
+            (Some(protocol), Some(value)) => (Some(protocol.to_uppercase()), value),
+            _ => return None
+        };
+
+        let value = value.trim_start_matches("0x").to_uppercase();
+        if value.is_empty() { return None }
+
+        Some(Self { protocol, value })
+    }
+
+    /// Checks for NEC repeat frame
+    pub fn is_repeat(&self) -> bool {
+        self.value == REPEAT_CODE
+    }
+
+    /// Returns code as number
+    pub fn as_u32(&self) -> Option<u32> {
+        u32::from_str_radix(&self.value, 16).ok()
+    }
+
+    /// Returns NEC address (high 16 bits of code)
+    pub fn address(&self) -> Option<u16> {
+        self.as_u32().map(|value| (value >> 16) as u16)
+    }
+}
+
+impl ::std::fmt::Display for Code {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        match &self.protocol {
+            Some(protocol) => write!(f, "{protocol} {}", self.value),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_code() {
+        assert_eq!(Code::parse("ff38c7"), Some(Code { protocol: None, value: "FF38C7".into() }));
+        assert_eq!(Code::parse("nec 0x20DF10EF"), Some(Code { protocol: Some("NEC".into()), value: "20DF10EF".into() }));
+        assert_eq!(Code::parse("  NEC   FF38C7  "), Some(Code { protocol: Some("NEC".into()), value: "FF38C7".into() }));
+    }
+
+    #[test]
+    fn rejects_empty_code() {
+        assert_eq!(Code::parse(""), None);
+        assert_eq!(Code::parse("   "), None);
+        assert_eq!(Code::parse("0x"), None);
+        assert_eq!(Code::parse("NEC 0x"), None);
+    }
+
+    #[test]
+    fn reads_repeat_and_address() {
+        assert!(Code::parse("FFFFFFFF").unwrap().is_repeat());
+        assert!(!Code::parse("FF38C7").unwrap().is_repeat());
+
+        assert_eq!(Code::parse("20DF10EF").unwrap().address(), Some(0x20DF));
+        assert_eq!(Code::parse("XYZ").unwrap().address(), None);
+    }
+}
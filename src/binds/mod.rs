@@ -1 +1,3 @@
-pub mod bind;  pub use bind::{ Bind, Action };
+pub mod bind;    pub use bind::{ Bind, Action, Repeat, RepeatPolicy, Trigger };
+pub mod code;    pub use code::{ Code, REPEAT_CODE };
+pub mod remote;  pub use remote::{ Remote, Resolved, DEFAULT_REMOTE };
@@ -0,0 +1,107 @@
+use crate::{ prelude::*, Bind, Code };
+
+/// The name of remote with config root binds (codes of no one remote)
+pub const DEFAULT_REMOTE: &str = "default";
+
+/// The remote definition
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct Remote {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+
+    #[serde(default)]
+    pub binds: HashMap<String, Bind>,
+}
+
+impl Remote {
+    /// Checks the code was sent by this remote
+    pub fn matches(&self, code: &Code) -> bool {
+        // remote without matchers can't be recognized:
+        if self.protocol.is_none() && self.address.is_none() && self.prefix.is_none() {
+            return false;
+        }
+
+        // check protocol (if receiver reports it):
+        if let (Some(protocol), Some(code_protocol)) = (&self.protocol, &code.protocol) {
+            if !protocol.eq_ignore_ascii_case(code_protocol) { return false }
+        }
+
+        // check NEC address:
+        if let Some(address) = &self.address {
+            let address = u16::from_str_radix(address.trim_start_matches("0x"), 16).ok();
+            if address.is_none() || address != code.address() { return false }
+        }
+
+        // check code prefix:
+        if let Some(prefix) = &self.prefix {
+            if !code.value.starts_with(&prefix.to_uppercase()) { return false }
+        }
+
+        true
+    }
+}
+
+/// The code resolving result
+#[derive(Debug, Clone)]
+pub enum Resolved<'a> {
+    Bind { remote: &'a str, bind: &'a Bind },
+    Unbound { remote: &'a str },
+    Ignored,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote(protocol: Option<&str>, address: Option<&str>, prefix: Option<&str>) -> Remote {
+        Remote {
+            protocol: protocol.map(str::to_owned),
+            address: address.map(str::to_owned),
+            prefix: prefix.map(str::to_owned),
+            binds: HashMap::new(),
+        }
+    }
+
+    fn code(line: &str) -> Code {
+        Code::parse(line).unwrap()
+    }
+
+    #[test]
+    fn remote_without_matchers_matches_nothing() {
+        assert!(!remote(None, None, None).matches(&code("20DF10EF")));
+    }
+
+    #[test]
+    fn matches_by_address() {
+        let tv = remote(None, Some("0x20DF"), None);
+
+        assert!(tv.matches(&code("20DF10EF")));
+        assert!(!tv.matches(&code("00FF38C7")));
+    }
+
+    #[test]
+    fn matches_by_prefix() {
+        let tv = remote(None, None, Some("20df"));
+
+        assert!(tv.matches(&code("20DF10EF")));
+        assert!(!tv.matches(&code("00FF38C7")));
+    }
+
+    #[test]
+    fn matches_by_protocol_if_reported() {
+        let tv = remote(Some("nec"), Some("20DF"), None);
+
+        assert!(tv.matches(&code("NEC 20DF10EF")));
+        assert!(tv.matches(&code("20DF10EF")));
+        assert!(!tv.matches(&code("RC5 20DF10EF")));
+    }
+
+    #[test]
+    fn invalid_address_matches_nothing() {
+        assert!(!remote(None, Some("TV"), None).matches(&code("20DF10EF")));
+    }
+}
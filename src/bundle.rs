@@ -0,0 +1,194 @@
+use crate::{ prelude::*, Action, Bind, Remote, binds::DEFAULT_REMOTE };
+use std::fs;
+
+/// The current bundle format version
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// The shareable keymap bundle (binds, remotes and their scripts without machine-specific settings)
+///
+/// Navigation modes are built-in (not configurable), so they aren't bundled.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Bundle {
+    pub version: u32,
+    #[serde(default)]
+    pub binds: HashMap<String, Bind>,
+    #[serde(default)]
+    pub remotes: HashMap<String, Remote>,
+    #[serde(default)]
+    pub scripts: HashMap<String, String>,
+}
+
+/// The bundle import conflict
+#[derive(Debug, Clone, Display)]
+pub enum Conflict {
+    #[display = "Code '{1}' of remote '{0}' is already bound to '{2}' (bundle bind '{3}')"]
+    Code(String, String, String, String),
+
+    #[display = "Script '{0}' already exists with different content"]
+    Script(String),
+}
+
+impl Bundle {
+    /// Packs config binds, remotes and the referenced scripts (from the scripts directory)
+    pub fn export<P: AsRef<Path>>(config: &Config, scripts_dir: P) -> Result<Self> {
+        let mut scripts = HashMap::new();
+
+        let binds = config.binds.values()
+            .chain(config.remotes.values().flat_map(|remote| remote.binds.values()));
+
+        for bind in binds {
+            if let Action::Script { script, .. } = &bind.action {
+                if scripts.contains_key(script) { continue }
+
+                let source = fs::read_to_string(scripts_dir.as_ref().join(script))
+                    .map_err(|e| Error::ScriptError(script.clone(), e.to_string()))?;
+
+                scripts.insert(script.clone(), source);
+            }
+        }
+
+        Ok(Self {
+            version: BUNDLE_VERSION,
+            binds: config.binds.clone(),
+            remotes: config.remotes.clone(),
+            scripts,
+        })
+    }
+
+    /// Reads bundle from file
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let bundle: Self = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+        if bundle.version > BUNDLE_VERSION {
+            return Err(Error::UnsupportedBundle(bundle.version, BUNDLE_VERSION).into());
+        }
+
+        Ok(bundle)
+    }
+
+    /// Writes bundle to file
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+
+    /// Merges bundle into config and writes its scripts (conflicting entries are kept unless overwrite is set)
+    pub fn merge<P: AsRef<Path>>(self, config: &mut Config, scripts_dir: P, overwrite: bool) -> Result<Vec<Conflict>> {
+        let mut conflicts = vec![];
+
+        // check script names and the bind scripts (they mustn't leave the scripts directory):
+        let bind_scripts = self.binds.values()
+            .chain(self.remotes.values().flat_map(|remote| remote.binds.values()))
+            .filter_map(|bind| match &bind.action {
+                Action::Script { script, .. } => Some(script),
+                _ => None,
+            });
+
+        for name in self.scripts.keys().chain(bind_scripts) {
+            if !is_relative_name(name) {
+                return Err(Error::InvalidBundleScript(name.clone()).into());
+            }
+        }
+
+        // merge default binds:
+        conflicts.extend(merge_binds(&mut config.binds, self.binds, DEFAULT_REMOTE, overwrite));
+
+        // merge remotes (existing remote keeps its protocol and address):
+        for (name, remote) in self.remotes {
+            match config.remotes.get_mut(&name) {
+                Some(existing) => conflicts.extend(merge_binds(&mut existing.binds, remote.binds, &name, overwrite)),
+                None => { config.remotes.insert(name, remote); }
+            }
+        }
+
+        // write scripts:
+        for (name, source) in self.scripts {
+            let path = scripts_dir.as_ref().join(&name);
+
+            if let Ok(existing) = fs::read_to_string(&path) {
+                if existing == source { continue }
+
+                conflicts.push(Conflict::Script(name));
+                if !overwrite { continue }
+            }
+
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&path, source)?;
+        }
+
+        Ok(conflicts)
+    }
+}
+
+/// Checks the script name is a plain relative path (has normal components only)
+fn is_relative_name(name: &str) -> bool {
+    let path = Path::new(name);
+
+    path.components().next().is_some() && path.components().all(|part| matches!(part, std::path::Component::Normal(_)))
+}
+
+/// Merges binds by code (returns conflicts with the differently named binds)
+fn merge_binds(binds: &mut HashMap<String, Bind>, incoming: HashMap<String, Bind>, remote: &str, overwrite: bool) -> Vec<Conflict> {
+    let mut conflicts = vec![];
+
+    for (code, bind) in incoming {
+        // codes are matched case-insensitively:
+        let existing = binds.keys().find(|key| key.eq_ignore_ascii_case(&code)).cloned();
+
+        match existing {
+            Some(key) if binds[&key].name != bind.name => {
+                conflicts.push(Conflict::Code(remote.to_owned(), key.clone(), binds[&key].name.clone(), bind.name.clone()));
+
+                if overwrite {
+                    binds.remove(&key);
+                    binds.insert(code, bind);
+                }
+            }
+            Some(key) => {
+                binds.remove(&key);
+                binds.insert(code, bind);
+            }
+            None => { binds.insert(code, bind); }
+        }
+    }
+
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_script_names() {
+        assert!(is_relative_name("volume.rhai"));
+        assert!(is_relative_name("media/next.rhai"));
+
+        assert!(!is_relative_name(""));
+        assert!(!is_relative_name("/etc/passwd"));
+        assert!(!is_relative_name("../secret.rhai"));
+        assert!(!is_relative_name("./volume.rhai"));
+        assert!(!is_relative_name("media/../../secret.rhai"));
+    }
+
+    #[test]
+    fn rejects_escaping_bind_script() {
+        let bind: Bind = serde_json::from_value(serde_json::json!({
+            "name": "Evil",
+            "action": { "script": "../evil.rhai" },
+        })).unwrap();
+
+        let bundle = Bundle {
+            version: BUNDLE_VERSION,
+            binds: hash_map! { str!("0x20DF10EF") => bind },
+            ..Default::default()
+        };
+
+        let dir = std::env::temp_dir().join("bundle-escaping-script");
+        assert!(bundle.merge(&mut Config::default(), &dir, false).is_err());
+        assert!(!dir.exists());
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{ prelude::*, Mode, control::ControlRequest };
+use clap::{ Parser, Subcommand, Args };
+
+/// The command-line interface
+#[derive(Debug, Parser)]
+#[command(name = "avr-pc-remote", version, about)]
+pub struct Cli {
+    #[command(flatten)]
+    pub run: RunArgs,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// The input reader options
+#[derive(Debug, Clone, Default, Args)]
+pub struct RunArgs {
+    /// Records raw input lines to file (for replay)
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+
+    /// Logs keys, mouse and system commands instead of executing them
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// The program command
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Runs the remote input reader (default)
+    Run(RunArgs),
+
+    /// Runs the remote input reader with a terminal dashboard (live inputs, state, bind counters and logs)
+    Tui(RunArgs),
+
+    /// Feeds a recorded input file through the dispatcher
+    Replay {
+        /// The record file
+        file: PathBuf,
+
+        /// Speed multiplier (2.0 is twice faster, repeats are only faithful at 1.0)
+        #[arg(long, default_value_t = 1.0, value_parser = parse_speed)]
+        speed: f64,
+
+        /// Sends events without delays (repeat delays and intervals aren't reproduced)
+        #[arg(long, conflicts_with = "speed")]
+        fast: bool,
+
+        /// Logs keys, mouse and system commands instead of executing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Creates a virtual Arduino on a pseudo-terminal (reads commands from prompt or script)
+    Simulate {
+        /// The commands script (one command per line)
+        #[arg(long)]
+        script: Option<PathBuf>,
+    },
+
+    /// Prints the config JSON Schema (for editor autocompletion and validation)
+    Schema {
+        /// Writes the schema to file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Converts LIRC remotes ('lircd.conf') to the remote config
+    ImportLirc {
+        /// The LIRC config file
+        file: PathBuf,
+
+        /// The remote name (defaults to the LIRC remote name)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Suggests actions by the standard button names (KEY_VOLUMEUP, KEY_PLAY, ..)
+        #[arg(long)]
+        suggest: bool,
+
+        /// Adds remotes to config file (instead of printing them)
+        #[arg(long)]
+        merge: bool,
+    },
+
+    /// Exports binds, remotes and their scripts to a shareable bundle (without serial port settings)
+    Export {
+        /// The bundle file
+        file: PathBuf,
+    },
+
+    /// Merges a bundle into config (reports codes that are already bound)
+    Import {
+        /// The bundle file
+        file: PathBuf,
+
+        /// Replaces conflicting binds and scripts
+        #[arg(long)]
+        overwrite: bool,
+    },
+
+    /// Controls the running instance
+    Ctl {
+        #[command(subcommand)]
+        command: CtlCommand,
+    },
+
+    /// Prints a systemd user unit (or installs it)
+    SystemdUnit {
+        /// Writes the unit to '~/.config/systemd/user'
+        #[arg(long)]
+        install: bool,
+    },
+}
+
+/// The control client command
+#[derive(Debug, Clone, Subcommand)]
+pub enum CtlCommand {
+    /// Switches navigation mode (media, mouse, selector)
+    Mode { mode: String },
+
+    /// Fires a bind by name
+    Fire { bind: String },
+
+    /// Sends a remote code (e.g. 'FF38C7' or 'NEC FF38C7')
+    Code { code: String },
+
+    /// Reloads config file
+    Reload,
+
+    /// Pauses remote input handling
+    Pause,
+
+    /// Resumes remote input handling
+    Resume,
+
+    /// Shows the current state
+    State,
+
+    /// Shows the last logs
+    Logs {
+        /// Keeps printing new logs
+        #[arg(short, long)]
+        follow: bool,
+
+        /// Count of the last lines
+        #[arg(short = 'n', long, default_value_t = 50)]
+        lines: usize,
+    },
+}
+
+impl CtlCommand {
+    /// Converts command to the control request
+    pub fn to_request(&self) -> Result<ControlRequest> {
+        Ok(match self.clone() {
+            Self::Mode { mode } => {
+                let mode: Mode = serde_json::from_value(Value::String(mode.clone()))
+                    .map_err(|_| Error::ControlError(fmt!("unknown mode '{mode}'")))?;
+
+                ControlRequest::Mode { mode }
+            }
+            Self::Fire { bind } => ControlRequest::Fire { bind },
+            Self::Code { code } => ControlRequest::Code { code },
+            Self::Reload => ControlRequest::Reload,
+            Self::Pause => ControlRequest::Pause,
+            Self::Resume => ControlRequest::Resume,
+            Self::State => ControlRequest::State,
+            Self::Logs { follow, lines } => ControlRequest::Logs { follow, lines },
+        })
+    }
+}
+
+/// Parses replay speed (a finite positive number)
+fn parse_speed(value: &str) -> StdResult<f64, String> {
+    let speed: f64 = value.parse().map_err(|_| fmt!("'{value}' isn't a number"))?;
+
+    if !speed.is_finite() || speed <= 0.0 {
+        return Err(fmt!("speed must be a positive number, got '{value}'"));
+    }
+
+    Ok(speed)
+}
@@ -1,16 +1,201 @@
-use crate::{ prelude::*, Bind };
+use crate::{ prelude::*, Bind, Action, Key, PowerAction, Code, Remote, Resolved, binds::DEFAULT_REMOTE };
 use std::fs;
 
 /// The application config
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Config {
     #[serde(default, skip_serializing, skip_deserializing)]
     path: PathBuf,
 
     pub com_port: usize,
     pub baud_rate: usize,
+    #[serde(default)]
+    pub ports: Vec<PortConfig>,
 
     pub binds: HashMap<String, Bind>,
+
+    #[serde(default)]
+    pub remotes: HashMap<String, Remote>,
+    /// The allowed remote names (empty allows all, root binds need "default" to be listed)
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+
+    #[serde(default)]
+    pub sleep_timer: TimerConfig,
+
+    #[serde(default)]
+    pub audio: DeviceConfig,
+    #[serde(default)]
+    pub micro: DeviceConfig,
+
+    #[serde(default)]
+    pub power_commands: HashMap<PowerAction, Vec<String>>,
+
+    #[serde(default)]
+    pub mpris: MprisConfig,
+
+    #[serde(default)]
+    pub notifications: NotifyConfig,
+
+    #[serde(default = "Config::default_script_timeout")]
+    pub script_timeout_ms: u64,
+
+    #[serde(default = "Config::default_push_to_talk_release")]
+    pub push_to_talk_release_ms: u64,
+
+    #[serde(default = "Config::default_media_poll")]
+    pub media_poll_ms: u64,
+
+    /// The delay between keys of multi-key presses and releases (applied on reload too)
+    #[serde(default)]
+    pub key_delay_ms: u64,
+    /// The shortcut keys hold time (applied on reload too)
+    #[serde(default = "Config::default_shortcut_hold")]
+    pub shortcut_hold_ms: u64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub control_socket: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub web_ui: Option<String>,
+}
+
+/// The MPRIS media control config
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct MprisConfig {
+    pub enabled: bool,
+    pub player: Option<String>,
+    pub bus_address: Option<String>,
+    pub seek_seconds: i64,
+}
+
+impl ::std::default::Default for MprisConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            player: None,
+            bus_address: None,
+            seek_seconds: 10,
+        }
+    }
+}
+
+/// The desktop notifications config
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct NotifyConfig {
+    pub enabled: bool,
+    pub mode: bool,
+    pub volume: bool,
+    pub devices: bool,
+    pub errors: bool,
+    pub coalesce_ms: u64,
+    pub timeout_ms: u64,
+}
+
+impl ::std::default::Default for NotifyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: true,
+            volume: true,
+            devices: true,
+            errors: true,
+            coalesce_ms: 1000,
+            timeout_ms: 3000,
+        }
+    }
+}
+
+/// The serial port config
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PortConfig {
+    pub name: String,
+    pub port: String,
+    #[serde(default = "PortConfig::default_baud_rate")]
+    pub baud_rate: u32,
+}
+
+impl PortConfig {
+    fn default_baud_rate() -> u32 { 9600 }
+}
+
+/// The device name pattern (substring or regex)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Pattern {
+    Regex { regex: String },
+    Substring(String),
+}
+
+/// The device cycle list entry (device name with optional alias)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum CycleEntry {
+    Alias { name: String, alias: String },
+    Name(String),
+}
+
+impl CycleEntry {
+    /// Returns the device name (or its part)
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Alias { name, .. } | Self::Name(name) => name,
+        }
+    }
+
+    /// Returns the device alias (or name)
+    pub fn alias(&self) -> &str {
+        match self {
+            Self::Alias { alias, .. } => alias,
+            Self::Name(name) => name,
+        }
+    }
+}
+
+/// The media devices config (filters and cycle order)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DeviceConfig {
+    pub include: Vec<Pattern>,
+    pub exclude: Vec<Pattern>,
+    pub cycle: Vec<CycleEntry>,
+}
+
+/// The sleep timer config
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct TimerConfig {
+    pub step_minutes: u64,
+    pub max_minutes: u64,
+    pub actions: Vec<Action>,
+}
+
+impl TimerConfig {
+    /// Returns the countdown step
+    pub fn step(&self) -> Duration {
+        Duration::from_secs(self.step_minutes * 60)
+    }
+
+    /// Returns the countdown limit
+    pub fn max(&self) -> Duration {
+        Duration::from_secs(self.max_minutes * 60)
+    }
+}
+
+impl ::std::default::Default for TimerConfig {
+    fn default() -> Self {
+        Self {
+            step_minutes: 15,
+            max_minutes: 180,
+            actions: vec![
+                Action::Press { press: vec![Key::MediaPlayPause] },
+                Action::Handler { handler: str!("sleep-mode"), args: Value::Null },
+            ],
+        }
+    }
 }
 
 impl Config {
@@ -43,13 +228,23 @@ impl Config {
         // read file:
         let json_str = fs::read_to_string(&path)?;
 
+        // parse json:
         let mut cfg: Config = serde_json::from_str(&json_str)?;
         cfg.path = path;
 
-        // parse json:
         Ok(cfg)
     }
-    
+
+    /// Reads config again from its file
+    pub fn reload(&self) -> Result<Self> {
+        let json_str = fs::read_to_string(&self.path)?;
+
+        let mut cfg: Config = serde_json::from_str(&json_str)?;
+        cfg.path = self.path.clone();
+
+        Ok(cfg)
+    }
+
     /// Updates a config file
     pub fn save(&mut self) -> Result<()> {
         self.save_to(&self.path.clone())
@@ -72,6 +267,114 @@ impl Config {
         
         Ok(())
     }
+
+    fn default_script_timeout() -> u64 { 500 }
+    fn default_push_to_talk_release() -> u64 { 250 }
+    fn default_media_poll() -> u64 { 2000 }
+    fn default_shortcut_hold() -> u64 { 100 }
+
+    /// Returns all config actions with their labels (binds and sleep timer actions)
+    pub fn actions(&self) -> Vec<(String, &Action)> {
+        let binds = self.binds.iter()
+            .chain(self.remotes.values().flat_map(|remote| remote.binds.iter()))
+            .map(|(code, bind)| (fmt!("{code} ({})", bind.name), &bind.action));
+
+        let timer_actions = self.sleep_timer.actions.iter()
+            .map(|action| (str!("sleep_timer"), action));
+
+        binds.chain(timer_actions).collect()
+    }
+
+    /// Finds a bind by name (in default and remote binds)
+    pub fn find_bind(&self, name: &str) -> Option<&Bind> {
+        self.binds.values()
+            .chain(self.remotes.values().flat_map(|remote| remote.binds.values()))
+            .find(|bind| bind.name == name)
+    }
+
+    /// Returns binds of remote by name (the default remote binds are the config root binds)
+    pub fn remote_binds_mut(&mut self, remote: &str) -> Option<&mut HashMap<String, Bind>> {
+        if remote == DEFAULT_REMOTE {
+            return Some(&mut self.binds);
+        }
+
+        self.remotes.get_mut(remote).map(|remote| &mut remote.binds)
+    }
+
+    /// Returns sorted unique bind names (default and remote binds)
+    pub fn bind_names(&self) -> Vec<String> {
+        let mut names = self.binds.values()
+            .chain(self.remotes.values().flat_map(|remote| remote.binds.values()))
+            .map(|bind| bind.name.clone())
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+
+        names
+    }
+
+    /// Finds a bind code by bind name (with the protocol of its remote)
+    pub fn find_code(&self, name: &str) -> Option<Code> {
+        if let Some((value, _)) = self.binds.iter().find(|(_, bind)| bind.name == name) {
+            return Some(Code { protocol: None, value: value.to_uppercase() });
+        }
+
+        self.remotes.values().find_map(|remote| {
+            let (value, _) = remote.binds.iter().find(|(_, bind)| bind.name == name)?;
+
+            Some(Code {
+                protocol: remote.protocol.as_ref().map(|protocol| protocol.to_uppercase()),
+                value: value.to_uppercase(),
+            })
+        })
+    }
+
+    /// Returns input ports list (or the single 'com_port' if it's not set)
+    pub fn get_ports(&self) -> Vec<PortConfig> {
+        if !self.ports.is_empty() {
+            return self.ports.clone();
+        }
+
+        vec![
+            PortConfig {
+                name: str!("main"),
+                port: fmt!("COM{}", self.com_port),
+                baud_rate: self.baud_rate as u32,
+            }
+        ]
+    }
+
+    /// Finds the remote which sent the code (returns None if remote isn't allowed, unmatched codes belong to "default")
+    pub fn find_remote(&self, code: &Code) -> Option<&str> {
+        let mut names = self.remotes.keys().collect::<Vec<_>>();
+        names.sort();
+
+        let name = names.into_iter()
+            .find(|name| self.remotes[*name].matches(code))
+            .map(|name| name.as_str())
+            .unwrap_or(DEFAULT_REMOTE);
+
+        if self.allowlist.is_empty() || self.allowlist.iter().any(|allowed| allowed == name) {
+            Some(name)
+        } else {
+            None
+        }
+    }
+
+    /// Resolves received code to the remote bind
+    pub fn resolve(&self, code: &Code) -> Resolved<'_> {
+        let Some(remote) = self.find_remote(code) else { return Resolved::Ignored };
+
+        let binds = match self.remotes.get(remote) {
+            Some(remote) => &remote.binds,
+            None => &self.binds,
+        };
+
+        match binds.get(&code.value) {
+            Some(bind) => Resolved::Bind { remote, bind },
+            None => Resolved::Unbound { remote },
+        }
+    }
 }
 
 impl ::std::default::Default for Config {
@@ -81,8 +384,96 @@ impl ::std::default::Default for Config {
 
             com_port: 8,
             baud_rate: 9600,
+            ports: vec![],
 
             binds: hash_map!{},
+
+            remotes: hash_map!{},
+            allowlist: vec![],
+
+            sleep_timer: TimerConfig::default(),
+
+            audio: DeviceConfig::default(),
+            micro: DeviceConfig::default(),
+
+            power_commands: hash_map!{},
+
+            mpris: MprisConfig::default(),
+
+            notifications: NotifyConfig::default(),
+
+            script_timeout_ms: Self::default_script_timeout(),
+
+            push_to_talk_release_ms: Self::default_push_to_talk_release(),
+
+            media_poll_ms: Self::default_media_poll(),
+
+            key_delay_ms: 0,
+            shortcut_hold_ms: Self::default_shortcut_hold(),
+
+            control_socket: None,
+            pid_file: None,
+            web_ui: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bind(name: &str) -> Bind {
+        serde_json::from_value(serde_json::json!({ "name": name })).unwrap()
+    }
+
+    fn with_allowlist(allowlist: &[&str]) -> Config {
+        let mut config = Config::default();
+
+        config.binds.insert(str!("FF38C7"), bind("root"));
+        config.remotes.insert(str!("tv"), Remote {
+            address: Some(str!("20DF")),
+            binds: hash_map!{ str!("20DF10EF") => bind("tv power") },
+            ..Remote::default()
+        });
+        config.allowlist = allowlist.iter().map(|name| name.to_string()).collect();
+
+        config
+    }
+
+    fn resolve(config: &Config, line: &str) -> Option<(String, Option<String>)> {
+        match config.resolve(&Code::parse(line).unwrap()) {
+            Resolved::Bind { remote, bind } => Some((remote.to_owned(), Some(bind.name.clone()))),
+            Resolved::Unbound { remote } => Some((remote.to_owned(), None)),
+            Resolved::Ignored => None,
+        }
+    }
+
+    #[test]
+    fn resolves_remote_binds() {
+        let config = with_allowlist(&[]);
+
+        assert_eq!(resolve(&config, "20DF10EF"), Some((str!("tv"), Some(str!("tv power")))));
+        assert_eq!(resolve(&config, "20DF40BF"), Some((str!("tv"), None)));
+    }
+
+    #[test]
+    fn resolves_default_binds() {
+        let config = with_allowlist(&[]);
+
+        assert_eq!(resolve(&config, "FF38C7"), Some((str!(DEFAULT_REMOTE), Some(str!("root")))));
+        assert_eq!(resolve(&config, "FF18E7"), Some((str!(DEFAULT_REMOTE), None)));
+    }
+
+    #[test]
+    fn allowlist_ignores_unlisted_remotes() {
+        let config = with_allowlist(&["tv"]);
+
+        assert_eq!(resolve(&config, "20DF10EF"), Some((str!("tv"), Some(str!("tv power")))));
+        assert_eq!(resolve(&config, "FF38C7"), None);
+
+        let config = with_allowlist(&["default"]);
+
+        assert_eq!(resolve(&config, "20DF10EF"), None);
+        assert_eq!(resolve(&config, "FF38C7"), Some((str!(DEFAULT_REMOTE), Some(str!("root")))));
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{ prelude::*, control::{ ControlRequest, ControlResponse } };
+use tokio::io::{ AsyncRead, AsyncWrite, AsyncBufReadExt, AsyncWriteExt, BufReader, Lines };
+
+type BoxReader = Box<dyn AsyncRead + Unpin + Send>;
+type BoxWriter = Box<dyn AsyncWrite + Unpin + Send>;
+
+/// The control socket client
+pub struct ControlClient {
+    lines: Lines<BufReader<BoxReader>>,
+    writer: BoxWriter,
+}
+
+impl ControlClient {
+    /// Connects to the running instance
+    pub async fn connect(path: &str) -> Result<Self> {
+        #[cfg(unix)]
+        let stream = tokio::net::UnixStream::connect(path).await?;
+
+        #[cfg(windows)]
+        let stream = tokio::net::windows::named_pipe::ClientOptions::new().open(path)?;
+
+        let (reader, writer) = tokio::io::split(stream);
+
+        Ok(Self {
+            lines: BufReader::new(Box::new(reader) as BoxReader).lines(),
+            writer: Box::new(writer),
+        })
+    }
+
+    /// Sends a request
+    pub async fn send(&mut self, request: &ControlRequest) -> Result<()> {
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+
+        self.writer.write_all(line.as_bytes()).await?;
+        self.writer.flush().await?;
+
+        Ok(())
+    }
+
+    /// Reads the next response (None if the connection is closed)
+    pub async fn next(&mut self) -> Result<Option<ControlResponse>> {
+        match self.lines.next_line().await? {
+            Some(line) => Ok(Some(serde_json::from_str(&line)?)),
+            None => Ok(None),
+        }
+    }
+}
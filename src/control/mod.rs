@@ -0,0 +1,70 @@
+pub mod server;     pub use server::ControlServer;
+pub mod client;     pub use client::ControlClient;
+
+use crate::{ prelude::*, Mode };
+use tokio::sync::oneshot;
+
+/// The control request (one JSON object per line)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+pub enum ControlRequest {
+    Mode { mode: Mode },
+    Fire { bind: String },
+    Code { code: String },
+    Reload,
+    Pause,
+    Resume,
+    State,
+    Logs {
+        #[serde(default)]
+        follow: bool,
+        #[serde(default = "ControlRequest::default_log_lines")]
+        lines: usize,
+    },
+}
+
+impl ControlRequest {
+    fn default_log_lines() -> usize { 50 }
+}
+
+/// The control response (log lines are followed by the final response)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "kebab-case")]
+pub enum ControlResponse {
+    Ok { message: String },
+    State { state: Value },
+    Log { line: String },
+    Error { message: String },
+}
+
+impl ControlResponse {
+    /// Creates a success response
+    pub fn ok<S: Into<String>>(message: S) -> Self {
+        Self::Ok { message: message.into() }
+    }
+
+    /// Checks the response is the last one for the request
+    pub fn is_final(&self) -> bool {
+        !matches!(self, Self::Log { .. })
+    }
+}
+
+/// The control request sent to dispatcher
+#[derive(Debug)]
+pub struct ControlMessage {
+    pub request: ControlRequest,
+    pub reply: oneshot::Sender<ControlResponse>,
+}
+
+/// Returns the control socket path (configured or platform default)
+pub fn socket_path(config: &Config) -> String {
+    if let Some(path) = &config.control_socket {
+        return path.clone();
+    }
+
+    if cfg!(windows) {
+        str!(r"\\.\pipe\avr-pc-remote")
+    } else {
+        crate::service::runtime_dir().join("avr-pc-remote.sock").to_string_lossy().to_string()
+    }
+}
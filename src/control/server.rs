@@ -0,0 +1,127 @@
+use crate::{ prelude::*, control::{ ControlRequest, ControlResponse, ControlMessage } };
+use tokio::io::{ AsyncRead, AsyncWrite, AsyncBufReadExt, AsyncWriteExt, BufReader };
+use tokio::sync::{ mpsc::Sender, oneshot, broadcast::error::RecvError };
+use tokio::task::JoinHandle;
+
+/// The control socket server (Unix socket or Windows named pipe)
+#[derive(Debug, Clone)]
+pub struct ControlServer {
+    path: String,
+    sender: Sender<ControlMessage>,
+}
+
+impl ControlServer {
+    /// Creates a new control server (requests are sent to dispatcher)
+    pub fn new<S: Into<String>>(path: S, sender: Sender<ControlMessage>) -> Self {
+        Self {
+            path: path.into(),
+            sender,
+        }
+    }
+
+    /// Starts accepting clients in background
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            if let Err(e) = self.listen().await {
+                err!("Error with control socket '{}': {e}", self.path);
+            }
+        })
+    }
+
+    /// Accepts Unix socket clients
+    #[cfg(unix)]
+    async fn listen(&self) -> Result<()> {
+        // remove the socket of previous run:
+        let _ = std::fs::remove_file(&self.path);
+
+        let listener = tokio::net::UnixListener::bind(&self.path)?;
+        info!("Listening control socket '{}'..", self.path);
+
+        loop {
+            let (stream, _) = listener.accept().await?;
+            tokio::spawn(serve(stream, self.sender.clone()));
+        }
+    }
+
+    /// Accepts named pipe clients
+    #[cfg(windows)]
+    async fn listen(&self) -> Result<()> {
+        use tokio::net::windows::named_pipe::ServerOptions;
+
+        let mut server = ServerOptions::new().first_pipe_instance(true).create(&self.path)?;
+        info!("Listening control pipe '{}'..", self.path);
+
+        loop {
+            server.connect().await?;
+
+            // create the next pipe instance before serving the client:
+            let client = std::mem::replace(&mut server, ServerOptions::new().create(&self.path)?);
+            tokio::spawn(serve(client, self.sender.clone()));
+        }
+    }
+}
+
+/// Serves a control client
+async fn serve<S: AsyncRead + AsyncWrite + Unpin>(stream: S, sender: Sender<ControlMessage>) {
+    if let Err(e) = serve_requests(stream, sender).await {
+        err!("Error with control client: {e}");
+    }
+}
+
+/// Reads client requests until disconnection
+async fn serve_requests<S: AsyncRead + AsyncWrite + Unpin>(stream: S, sender: Sender<ControlMessage>) -> Result<()> {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        let request: ControlRequest = match serde_json::from_str(&line) {
+            Ok(request) => request,
+            Err(e) => {
+                respond(&mut writer, &ControlResponse::Error { message: e.to_string() }).await?;
+                continue;
+            }
+        };
+
+        match request {
+            // logs are served without dispatcher:
+            ControlRequest::Logs { follow, lines: count } => {
+                let mut logs = LOGGER.subscribe();
+
+                for line in LOGGER.recent(count) {
+                    respond(&mut writer, &ControlResponse::Log { line }).await?;
+                }
+
+                while follow {
+                    match logs.recv().await {
+                        Ok(line) => respond(&mut writer, &ControlResponse::Log { line }).await?,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    }
+                }
+
+                respond(&mut writer, &ControlResponse::ok("")).await?;
+            }
+
+            request => {
+                let (reply, response) = oneshot::channel();
+                sender.send(ControlMessage { request, reply }).await.map_err(|_| Error::DispatcherStopped)?;
+
+                let response = response.await.map_err(|_| Error::DispatcherStopped)?;
+                respond(&mut writer, &response).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a response line
+async fn respond<W: AsyncWrite + Unpin>(writer: &mut W, response: &ControlResponse) -> Result<()> {
+    let mut line = serde_json::to_string(response)?;
+    line.push('\n');
+
+    writer.write_all(line.as_bytes()).await?;
+    writer.flush().await?;
+
+    Ok(())
+}
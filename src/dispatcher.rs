@@ -0,0 +1,508 @@
+use crate::{ prelude::*, Action, Bind, Code, Config, InputEvent, Recorder, Notifier, PollerHandle, Notification, NotifyKind, Resolved, State, Snapshot, Trigger, ScriptEngine, ScriptCommand, ScriptEnv, ControlRequest, ControlResponse, ControlMessage, handlers::{ Context, Devices, Registry } };
+use tokio::sync::mpsc::Receiver;
+use tokio::sync::broadcast;
+
+/// The dispatcher event (for live monitoring)
+#[derive(Debug, Clone)]
+pub enum DispatchEvent {
+    Input { source: String, code: String, remote: Option<String>, bind: Option<String> },
+    Fired { bind: String, repeats: u32, error: Option<String> },
+}
+
+/// The input source state
+struct SourceState {
+    last_bind: Option<Bind>,
+    push_to_talk: bool,
+    first_press: Instant,
+    last_action: Instant,
+    repeats: u32,
+}
+
+impl ::std::default::Default for SourceState {
+    fn default() -> Self {
+        Self {
+            last_bind: None,
+            push_to_talk: false,
+            first_press: Instant::now(),
+            last_action: Instant::now(),
+            repeats: 0,
+        }
+    }
+}
+
+/// The input events dispatcher
+pub struct Dispatcher {
+    pub devices: Devices,
+    pub state: State,
+
+    config: Config,
+    registry: Registry,
+    scripts: ScriptEngine,
+    sources: HashMap<String, SourceState>,
+    paused: bool,
+    recorder: Option<Recorder>,
+    notifier: Option<Notifier>,
+    poller: Option<PollerHandle>,
+    events: broadcast::Sender<DispatchEvent>,
+
+    last_timer_report: Instant,
+}
+
+impl Dispatcher {
+    const TIMER_REPORT_INTERVAL: Duration = Duration::from_secs(60);
+
+    /// Creates a new dispatcher (checks config bind handlers and scripts)
+    pub fn new(config: Config, registry: Registry, devices: Devices) -> Result<Self> {
+        registry.validate_config(&config)?;
+
+        let scripts = ScriptEngine::new(root_path("/scripts")?);
+        Self::compile_scripts(&scripts, &config)?;
+
+        Ok(Self {
+            devices,
+            state: State::default(),
+
+            config,
+            registry,
+            scripts,
+            sources: HashMap::new(),
+            paused: false,
+            recorder: None,
+            notifier: None,
+            poller: None,
+            events: broadcast::channel(256).0,
+
+            last_timer_report: Instant::now(),
+        })
+    }
+
+    /// Returns the config
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns the handlers registry
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Subscribes to the dispatcher events
+    pub fn subscribe(&self) -> broadcast::Receiver<DispatchEvent> {
+        self.events.subscribe()
+    }
+
+    /// Returns the events sender (to subscribe later)
+    pub fn event_sender(&self) -> broadcast::Sender<DispatchEvent> {
+        self.events.clone()
+    }
+
+    /// Records raw input lines to file
+    pub fn record_to(&mut self, recorder: Recorder) {
+        self.recorder = Some(recorder);
+    }
+
+    /// Shows desktop notifications about bind changes and errors
+    pub fn notify_to(&mut self, notifier: Notifier) {
+        self.notifier = Some(notifier);
+    }
+
+    /// Keeps the media poller to stop it on shutdown
+    pub fn poll_media(&mut self, poller: PollerHandle) {
+        self.poller = Some(poller);
+    }
+
+    /// Checks config bind handlers and scripts without applying it (e.g. before saving an edited config)
+    pub fn check_config(registry: &Registry, config: &Config) -> Result<()> {
+        registry.validate_config(config)?;
+
+        Self::compile_scripts(&ScriptEngine::new(root_path("/scripts")?), config)
+    }
+
+    /// Checks the config scripts compile
+    fn compile_scripts(scripts: &ScriptEngine, config: &Config) -> Result<()> {
+        for (label, action) in config.actions() {
+            if let Action::Script { script, .. } = action {
+                scripts.compile(script)
+                    .map_err(|e| Error::InvalidBind(label, e.to_string()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handles input events and control requests until all input sources are closed
+    pub async fn run(&mut self, mut receiver: Receiver<InputEvent>, mut control: Receiver<ControlMessage>) -> Result<()> {
+        info!("Reading remote inputs..");
+
+        loop {
+            self.tick().await?;
+
+            tokio::select! {
+                event = receiver.recv() => match event {
+                    Some(event) => {
+                        if let Some(recorder) = &mut self.recorder {
+                            if let Err(e) = recorder.record(&event) {
+                                err!("Error with recording input: {e}");
+                            }
+                        }
+
+                        if !self.paused { self.handle_input(&event).await }
+                    },
+                    None => return Err(Error::NoInputPorts.into()),
+                },
+                Some(message) = control.recv() => {
+                    let response = self.handle_control(message.request).await;
+                    let _ = message.reply.send(response);
+                },
+                _ = sleep(Duration::from_millis(10)) => {},
+            }
+        }
+    }
+
+    /// Releases held keys and mouse buttons, stops push-to-talk (before exit)
+    pub async fn shutdown(&mut self) {
+        if let Err(e) = self.devices.keyboard.release_held().await {
+            err!("Error with releasing keys: {e}");
+        }
+        if let Err(e) = self.devices.mouse.release_held() {
+            err!("Error with releasing mouse buttons: {e}");
+        }
+
+        if self.state.push_to_talk.take().is_some() {
+            if let Err(e) = self.devices.media.set_micro_mute(true).await {
+                err!("Error with muting microphone: {e}");
+            }
+        }
+
+        if let Some(poller) = self.poller.take() {
+            poller.stop().await;
+        }
+    }
+
+    /// Handles a control request
+    pub async fn handle_control(&mut self, request: ControlRequest) -> ControlResponse {
+        match self.execute_control(request).await {
+            Ok(response) => response,
+            Err(e) => ControlResponse::Error { message: e.to_string() },
+        }
+    }
+
+    /// Executes a control request
+    async fn execute_control(&mut self, request: ControlRequest) -> Result<ControlResponse> {
+        Ok(match request {
+            ControlRequest::Mode { mode } => {
+                let name = mode.to_string().to_lowercase();
+
+                self.state.mode = mode;
+                info!("Switched to {name} mode");
+
+                ControlResponse::ok(fmt!("Switched to {name} mode"))
+            }
+            ControlRequest::Fire { bind: name } => {
+                let bind = self.config.find_bind(&name).cloned().ok_or_else(|| Error::BindNotFound(name.clone()))?;
+                info!("Fired bind '{name}' by control request");
+
+                self.execute_bind(&bind, &Trigger::new(0, &bind)).await?;
+                ControlResponse::ok(fmt!("Fired bind '{name}'"))
+            }
+            ControlRequest::Code { code } => {
+                self.handle_input(&InputEvent { source: str!("ctl"), line: code.clone() }).await;
+                ControlResponse::ok(fmt!("Sent code '{code}'"))
+            }
+            ControlRequest::Reload => {
+                self.reload().await?;
+                ControlResponse::ok("Config reloaded")
+            }
+            ControlRequest::Pause => {
+                self.paused = true;
+                info!("Remote input paused");
+
+                ControlResponse::ok("Remote input paused")
+            }
+            ControlRequest::Resume => {
+                self.paused = false;
+                info!("Remote input resumed");
+
+                ControlResponse::ok("Remote input resumed")
+            }
+            ControlRequest::State => ControlResponse::State { state: self.report() },
+            ControlRequest::Logs { .. } => ControlResponse::Error { message: str!("logs are served by control server") },
+        })
+    }
+
+    /// Reloads config from file (ports, device filters, MPRIS and notifications settings are applied after restart)
+    pub async fn reload(&mut self) -> Result<()> {
+        let config = self.config.reload()?;
+
+        self.registry.validate_config(&config)?;
+        Self::compile_scripts(&self.scripts, &config)?;
+
+        *CONFIG.lock().await = config.clone();
+        self.devices.keyboard.set_key_delay(Duration::from_millis(config.key_delay_ms));
+        self.config = config;
+        self.sources.clear();
+
+        info!("Config reloaded");
+        Ok(())
+    }
+
+    /// Returns the state report
+    pub fn report(&self) -> Value {
+        let media = self.devices.media.get_info();
+
+        serde_json::json!({
+            "mode": self.state.mode,
+            "paused": self.paused,
+            "sleep_timer": self.state.sleep_timer.state().to_string(),
+            "push_to_talk": self.state.push_to_talk.is_some(),
+            "undo_history": self.state.history.len(),
+            "audio_device": media.active.map(|device| device.name),
+            "volume": media.volume,
+            "audio_muted": media.audio_muted,
+            "micro_muted": media.micro_muted,
+        })
+    }
+
+    /// Executes periodic tasks (sleep timer, push-to-talk release)
+    pub async fn tick(&mut self) -> Result<()> {
+        // check push-to-talk release:
+        if self.state.push_to_talk.map(|deadline| Instant::now() >= deadline).unwrap_or(false) {
+            self.state.push_to_talk = None;
+
+            match self.devices.media.set_micro_mute(true).await {
+                Ok(_) => info!("Push-to-talk stopped"),
+                Err(e) => err!("Error with muting microphone: {e}"),
+            }
+        }
+
+        // check sleep timer:
+        if self.state.sleep_timer.poll_expired() {
+            info!("Sleep timer expired");
+
+            for action in self.config.sleep_timer.actions.clone() {
+                if let Err(e) = self.execute_action(&action, &Trigger::default()).await {
+                    err!("Error with executing sleep timer action: {e}");
+                }
+            }
+        }
+        else if self.state.sleep_timer.is_running() && self.last_timer_report.elapsed() >= Self::TIMER_REPORT_INTERVAL {
+            info!("Sleep timer: {}", self.state.sleep_timer.state());
+            self.last_timer_report = Instant::now();
+        }
+
+        Ok(())
+    }
+
+    /// Handles an input line
+    pub async fn handle_input(&mut self, event: &InputEvent) {
+        let Some(code) = Code::parse(&event.line) else { return };
+
+        // repeat last bind:
+        if code.is_repeat() {
+            let source = self.sources.entry(event.source.clone()).or_default();
+
+            // push-to-talk is held by every repeat frame (regardless of the repeat policy):
+            if source.push_to_talk && self.state.push_to_talk.is_some() {
+                self.state.push_to_talk = Some(Instant::now() + Duration::from_millis(self.config.push_to_talk_release_ms));
+            }
+
+            let Some(bind) = source.last_bind.clone() else { return };
+            let Some(policy) = bind.repeat.policy() else { return };
+
+            // check repeat policy:
+            if source.first_press.elapsed() < policy.delay()
+            || source.last_action.elapsed() < policy.interval()
+            || policy.is_exhausted(source.repeats) {
+                return;
+            }
+
+            source.repeats += 1;
+            source.last_action = Instant::now();
+            let trigger = Trigger::new(source.repeats, &bind);
+
+            if let Err(e) = self.execute_bind(&bind, &trigger).await {
+                err!("Error with executing bind: {e}");
+            }
+        } else {
+            let (remote, bind) = match self.config.resolve(&code) {
+                // execute exists bind:
+                Resolved::Bind { remote, bind } => {
+                    info!("Pressed '{code}' on '{remote}' remote ('{}' source), bind '{}'.", event.source, bind.name);
+                    (Some(remote.to_owned()), Some(bind.clone()))
+                }
+                // unbinded code:
+                Resolved::Unbound { remote } => {
+                    info!("Pressed '{code}' on '{remote}' remote ('{}' source), no binds exists..", event.source);
+                    (Some(remote.to_owned()), None)
+                }
+                // code of unknown remote:
+                Resolved::Ignored => (None, None),
+            };
+
+            let _ = self.events.send(DispatchEvent::Input {
+                source: event.source.clone(),
+                code: code.to_string(),
+                remote: remote.clone(),
+                bind: bind.as_ref().map(|bind| bind.name.clone()),
+            });
+
+            // ignored code doesn't touch the source state:
+            if remote.is_none() { return }
+
+            let source = self.sources.entry(event.source.clone()).or_default();
+            source.last_bind = bind.clone().filter(|bind| bind.repeat.is_enabled());
+            source.push_to_talk = bind.as_ref().map(holds_push_to_talk).unwrap_or(false);
+            source.first_press = Instant::now();
+            source.last_action = Instant::now();
+            source.repeats = 0;
+
+            if let Some(bind) = bind {
+                if let Err(e) = self.execute_bind(&bind, &Trigger::new(0, &bind)).await {
+                    err!("Error with executing bind: {e}");
+                }
+            }
+        }
+    }
+
+    /// Executes remote bind (remembers the previous state for undo)
+    pub async fn execute_bind(&mut self, bind: &Bind, trigger: &Trigger) -> Result<()> {
+        let before = Snapshot::capture(&self.devices.media, self.state.mode);
+        let undos = self.state.history.undos();
+        let result = self.execute_action(&bind.action, trigger).await;
+
+        let after = Snapshot::capture(&self.devices.media, self.state.mode);
+
+        if let Some(notifier) = &self.notifier {
+            notifier.notify_changes(&before, &after);
+
+            if let Err(e) = &result {
+                notifier.notify(Notification::new(NotifyKind::Error, fmt!("Bind '{}' failed", bind.name), e.to_string()));
+            }
+        }
+
+        self.state.history.record(before, &after, trigger.repeats > 0, undos);
+
+        let _ = self.events.send(DispatchEvent::Fired {
+            bind: bind.name.clone(),
+            repeats: trigger.repeats,
+            error: result.as_ref().err().map(|e| e.to_string()),
+        });
+
+        result
+    }
+
+    /// Executes bind action
+    pub async fn execute_action(&mut self, action: &Action, trigger: &Trigger) -> Result<()> {
+        match action {
+            // execute registered handler:
+            Action::Handler { handler: name, args } => {
+                let handler = self.registry.get(name).ok_or_else(|| Error::UnknownHandler(name.clone()))?;
+
+                let mut ctx = Context {
+                    devices: &mut self.devices,
+                    state: &mut self.state,
+                    config: &self.config,
+                    trigger,
+                };
+
+                handler.call(&mut ctx, args).await?;
+            },
+
+            // press keyboard shortcut:
+            Action::Shortcut { shortcut: keys } => {
+                self.devices.keyboard.hold_for(keys, Duration::from_millis(self.config.shortcut_hold_ms)).await?;
+            },
+
+            // press keyboard key:
+            Action::Press { press: keys } => {
+                self.devices.keyboard.press_all(keys, false).await?;
+            },
+
+            // hold keyboard keys (until the key up action or exit):
+            Action::KeyDown { key_down: keys } => {
+                self.devices.keyboard.press_all(keys, true).await?;
+            },
+
+            // release keyboard keys:
+            Action::KeyUp { key_up: keys } => {
+                self.devices.keyboard.release_all(keys).await?;
+            },
+
+            // hold keyboard keys for duration (they're released in background):
+            Action::Hold { hold: keys, duration_ms } => {
+                self.devices.keyboard.hold_in_background(keys, Duration::from_millis(*duration_ms)).await?;
+            },
+
+            // open website:
+            Action::Open { open: url } => {
+                let url = if url.starts_with("https:") { url }else{ &fmt!("https://{url}") };
+
+                if self.devices.dry_run {
+                    info!("[dry-run] open '{url}'");
+                } else {
+                    webbrowser::open(url)?;
+                }
+            },
+
+            // empty action (unassigned bind):
+            Action::None => {},
+
+            // run script:
+            Action::Script { script, timeout_ms } => {
+                let env = ScriptEnv {
+                    mode: self.state.mode,
+                    volume: self.devices.media.get_volume(),
+                    audio_device: self.devices.media.get_active().map(|device| device.name).unwrap_or_default(),
+                    repeats: trigger.repeats,
+                };
+                let timeout = Duration::from_millis(timeout_ms.unwrap_or(self.config.script_timeout_ms));
+                let deadline = Instant::now() + timeout;
+
+                for command in self.scripts.run(script, &env, timeout)? {
+                    // waits are clamped to the remaining script budget:
+                    let command = match command {
+                        ScriptCommand::Wait(millis) => {
+                            let remaining = deadline.saturating_duration_since(Instant::now()).as_millis() as u64;
+                            ScriptCommand::Wait(millis.min(remaining))
+                        }
+                        command => command,
+                    };
+
+                    self.execute_script_command(command, trigger).await?;
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Executes a command queued by script
+    async fn execute_script_command(&mut self, command: ScriptCommand, trigger: &Trigger) -> Result<()> {
+        match command {
+            ScriptCommand::Action(action) => Box::pin(self.execute_action(&action, trigger)).await?,
+            ScriptCommand::MouseMove(dx, dy) => {
+                self.devices.mouse.move_x(dx)?;
+                self.devices.mouse.move_y(dy)?;
+            }
+            ScriptCommand::MouseClick(right) => {
+                if right {
+                    self.devices.mouse.press_right(false)?;
+                } else {
+                    self.devices.mouse.press_left(false)?;
+                }
+            }
+            ScriptCommand::SetMode(mode) => {
+                self.state.mode = mode;
+                info!("Switched to {} mode", mode.to_string().to_lowercase());
+            }
+            ScriptCommand::Wait(millis) => sleep(Duration::from_millis(millis)).await,
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks the bind holds push-to-talk (it's released by the repeat frames stop)
+fn holds_push_to_talk(bind: &Bind) -> bool {
+    matches!(&bind.action, Action::Handler { handler, .. } if handler == "push-to-talk")
+}
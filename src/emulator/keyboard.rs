@@ -2,7 +2,7 @@ use crate::prelude::*;
 use enigo::{ Enigo, Key as EnigoKey, Keyboard as EnigoKeyboard, Settings, Direction };
 
 /// The keyboard key
-#[derive(Debug, Display, Clone, Serialize, Deserialize)]
+#[derive(Debug, Display, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum Key {
     Add,
     Alt,
@@ -289,7 +289,9 @@ impl ::std::convert::Into<EnigoKey> for Key {
 /// The keyboard emulator
 #[derive(Debug, Clone)]
 pub struct Keyboard {
-    enigo: Arc<Mutex<Enigo>>,
+    enigo: Option<Arc<Mutex<Enigo>>>,
+    held: Arc<StdMutex<Vec<Key>>>,
+    key_delay: Duration,
 }
 
 impl Keyboard {
@@ -298,21 +300,49 @@ impl Keyboard {
         let settings = Settings::default();
         
         Ok(Self {
-            enigo: Arc::new(Mutex::new(Enigo::new(&settings)?)),
+            enigo: Some(Arc::new(Mutex::new(Enigo::new(&settings)?))),
+            held: Arc::new(StdMutex::new(vec![])),
+            key_delay: Duration::ZERO,
         })
     }
 
+    /// Creates a dry-run keyboard (logs keys instead of pressing)
+    pub fn dry_run() -> Self {
+        Self {
+            enigo: None,
+            held: Arc::new(StdMutex::new(vec![])),
+            key_delay: Duration::ZERO,
+        }
+    }
+
+    /// Sets the delay between keys of multi-key presses and releases
+    pub fn with_key_delay(mut self, delay: Duration) -> Self {
+        self.key_delay = delay;
+        self
+    }
+
+    /// Changes the delay between keys (e.g. on config reload)
+    pub fn set_key_delay(&mut self, delay: Duration) {
+        self.key_delay = delay;
+    }
+
     /// Press a keyboard key
     pub async fn press(&self, key: &Key, hold: bool) -> Result<()> {
-        self.enigo.lock().await.key(key.clone().into(), if hold { Direction::Press }else{ Direction::Click })?;
+        self.key(key, if hold { Direction::Press }else{ Direction::Click }).await?;
+
+        if hold { self.hold(key) }
 
         Ok(())
     }
 
     /// Press an keyboard keys at the same time
     pub async fn press_all(&self, keys: &[Key], hold: bool) -> Result<()> {
-        for key in keys {
-            self.enigo.lock().await.key(key.clone().into(), if hold { Direction::Press }else{ Direction::Click })?;
+        for (index, key) in keys.iter().enumerate() {
+            if index > 0 { self.wait_key_delay().await }
+
+            self.key(key, if hold { Direction::Press }else{ Direction::Click }).await?;
+
+            if hold { self.hold(key) }
         }
 
         Ok(())
@@ -320,15 +350,83 @@ impl Keyboard {
 
     /// Release a keyboard key (if it's hold)
     pub async fn release(&self, key: &Key) -> Result<()> {
-        self.enigo.lock().await.key(key.clone().into(), Direction::Release)?;
+        self.key(key, Direction::Release).await?;
+        self.held.lock().unwrap().retain(|held| held != key);
 
         Ok(())
     }
 
     /// Release an keyboard keys at the same time (if it's hold)
     pub async fn release_all(&self, keys: &[Key]) -> Result<()> {
-        for key in keys {
-            self.enigo.lock().await.key(key.clone().into(), Direction::Release)?;
+        for (index, key) in keys.iter().enumerate() {
+            if index > 0 { self.wait_key_delay().await }
+
+            self.key(key, Direction::Release).await?;
+            self.held.lock().unwrap().retain(|held| held != key);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the held keys
+    pub fn get_held(&self) -> Vec<Key> {
+        self.held.lock().unwrap().clone()
+    }
+
+    /// Release all held keys
+    pub async fn release_held(&self) -> Result<()> {
+        let keys = self.get_held();
+
+        self.release_all(&keys).await
+    }
+
+    /// Holds keys for duration, then releases them
+    pub async fn hold_for(&self, keys: &[Key], duration: Duration) -> Result<()> {
+        self.press_all(keys, true).await?;
+        sleep(duration).await;
+
+        self.release_all(keys).await
+    }
+
+    /// Holds keys and releases them after duration in background (doesn't wait for it)
+    pub async fn hold_in_background(&self, keys: &[Key], duration: Duration) -> Result<()> {
+        self.press_all(keys, true).await?;
+
+        let keyboard = self.clone();
+        let keys = keys.to_vec();
+
+        tokio::spawn(async move {
+            sleep(duration).await;
+
+            if let Err(e) = keyboard.release_all(&keys).await {
+                err!("Error with releasing held keys: {e}");
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Waits the delay between keys
+    async fn wait_key_delay(&self) {
+        if !self.key_delay.is_zero() {
+            sleep(self.key_delay).await;
+        }
+    }
+
+    /// Marks the key as held
+    fn hold(&self, key: &Key) {
+        let mut held = self.held.lock().unwrap();
+
+        if !held.contains(key) {
+            held.push(key.clone());
+        }
+    }
+
+    /// Simulates key event (or logs it in dry-run mode)
+    async fn key(&self, key: &Key, direction: Direction) -> Result<()> {
+        match &self.enigo {
+            Some(enigo) => enigo.lock().await.key(key.clone().into(), direction)?,
+            None => info!("[dry-run] key '{key}' {direction:?}"),
         }
 
         Ok(())
@@ -1,8 +1,7 @@
-use crate::prelude::*;
-use std::process::Command;
+use crate::{ prelude::*, DeviceConfig, Pattern, CycleEntry, Session, AppTarget };
+use std::process::{ Command, ExitStatus, Output };
 use csv::Reader;
-
-pub type DeviceFilter = fn(&str) -> bool;
+use regex::Regex;
 
 /// The device kind
 #[derive(Debug, Display, Clone, Eq, PartialEq)]
@@ -24,13 +23,129 @@ impl DeviceKind {
 }
 
 /// The media device
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Device {
     pub name: String,
     pub kind: DeviceKind,
     pub is_active: bool,
 }
 
+/// The device name matcher
+#[derive(Debug, Clone)]
+enum Matcher {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl Matcher {
+    /// Checks the device name matches
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Substring(part) => name.contains(part.as_str()),
+            Self::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
+/// The media devices filter (include/exclude patterns and cycle order)
+#[derive(Debug, Clone, Default)]
+pub struct DeviceFilter {
+    include: Vec<Matcher>,
+    exclude: Vec<Matcher>,
+    cycle: Vec<CycleEntry>,
+}
+
+impl DeviceFilter {
+    /// Creates a filter by config (compiles regex patterns)
+    pub fn new(config: &DeviceConfig) -> Result<Self> {
+        let compile = |patterns: &[Pattern]| -> Result<Vec<Matcher>> {
+            patterns.iter()
+                .map(|pattern| Ok(match pattern {
+                    Pattern::Substring(part) => Matcher::Substring(part.clone()),
+                    Pattern::Regex { regex } => Matcher::Regex(
+                        Regex::new(regex).map_err(|e| Error::InvalidPattern(regex.clone(), e.to_string()))?
+                    ),
+                }))
+                .collect()
+        };
+
+        Ok(Self {
+            include: compile(&config.include)?,
+            exclude: compile(&config.exclude)?,
+            cycle: config.cycle.clone(),
+        })
+    }
+
+    /// Checks the device passes include/exclude patterns
+    pub fn accepts(&self, name: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|matcher| matcher.matches(name)))
+        && !self.exclude.iter().any(|matcher| matcher.matches(name))
+    }
+
+    /// Finds the cycle entry of device
+    fn entry(&self, name: &str) -> Option<&CycleEntry> {
+        self.cycle.iter().find(|entry| entry.name() == name)
+            .or_else(|| self.cycle.iter().find(|entry| name.contains(entry.name())))
+    }
+
+    /// Returns the device alias (or its name)
+    pub fn alias<'a>(&'a self, name: &'a str) -> &'a str {
+        self.entry(name).map(|entry| entry.alias()).unwrap_or(name)
+    }
+
+    /// Finds a device by name or alias
+    pub fn find<'a>(&self, devices: &'a [Device], name: &str) -> Option<&'a Device> {
+        devices.iter().find(|device| device.name == name)
+            .or_else(|| {
+                let entry = self.cycle.iter().find(|entry| entry.alias() == name)?;
+                devices.iter().find(|device| self.entry(&device.name).map(|e| e.name()) == Some(entry.name()))
+            })
+    }
+
+    /// Orders devices by cycle list (all devices if the list isn't set)
+    pub fn order(&self, devices: Vec<Device>) -> Vec<Device> {
+        if self.cycle.is_empty() {
+            return devices;
+        }
+
+        let mut ordered: Vec<Device> = vec![];
+
+        for entry in &self.cycle {
+            let device = devices.iter().find(|device| device.name == entry.name())
+                .or_else(|| devices.iter().find(|device| device.name.contains(entry.name())));
+
+            if let Some(device) = device {
+                if !ordered.iter().any(|d| d.name == device.name) {
+                    ordered.push(device.clone());
+                }
+            }
+        }
+
+        ordered
+    }
+}
+
+/// The media state info (shared between controller copies)
+#[derive(Debug, Clone, Default)]
+pub struct MediaInfo {
+    pub devices: Vec<Device>,
+    pub active: Option<Device>,
+    pub volume: i32,
+    pub audio_muted: bool,
+    pub micro_muted: bool,
+    revision: u64,
+}
+
+/// The media state change event
+#[derive(Debug, Clone)]
+pub enum MediaEvent {
+    DevicesChanged(Vec<Device>),
+    ActiveChanged(Option<Device>),
+    VolumeChanged(i32),
+    AudioMuteChanged(bool),
+    MicroMuteChanged(bool),
+}
+
 /// The media controller
 #[derive(Debug, Clone)]
 pub struct Media {
@@ -38,77 +153,143 @@ pub struct Media {
     svv_path: PathBuf,
     svcl_path: PathBuf,
 
-    device_filter: Option<DeviceFilter>,
-    devices: Vec<Device>,
-    active: Option<Device>,
-    volume: i32,
+    audio_filter: DeviceFilter,
+    micro_filter: DeviceFilter,
+    info: Arc<StdMutex<MediaInfo>>,
+    dry_run: bool,
 }
 
 impl Media {
     /// Creates a new audio controller
-    pub async fn new<P: AsRef<Path>>(bin_path: P, device_filter: Option<DeviceFilter>) -> Result<Self> {        
+    pub async fn new<P: AsRef<Path>>(bin_path: P, audio_filter: DeviceFilter, micro_filter: DeviceFilter) -> Result<Self> {
         let bin_path = bin_path.as_ref();
         
-        let mut this = Self {
+        let this = Self {
             nircmd_path: bin_path.join("nircmd/nircmd.exe"),
             svv_path: bin_path.join("svv/SoundVolumeView.exe"),
             svcl_path: bin_path.join("svcl/svcl.exe"),
 
-            device_filter,
-            devices: vec![],
-            active: None,
-            volume: 0,
+            audio_filter,
+            micro_filter,
+            info: Arc::new(StdMutex::new(MediaInfo::default())),
+            dry_run: false,
         };
 
         this.update_info().await?;
         
         Ok(this)
     }
+
+    /// Creates a dry-run audio controller (logs commands, uses virtual devices)
+    pub fn dry_run(audio_filter: DeviceFilter, micro_filter: DeviceFilter) -> Self {
+        let devices = vec![
+            Device { name: str!("Dry-run Speakers"), kind: DeviceKind::Audio, is_active: true },
+            Device { name: str!("Dry-run Microphone"), kind: DeviceKind::Micro, is_active: true },
+        ];
+
+        Self {
+            nircmd_path: PathBuf::new(),
+            svv_path: PathBuf::new(),
+            svcl_path: PathBuf::new(),
+
+            audio_filter,
+            micro_filter,
+            info: Arc::new(StdMutex::new(MediaInfo {
+                active: Some(devices[0].clone()),
+                devices,
+                volume: 50,
+                ..MediaInfo::default()
+            })),
+            dry_run: true,
+        }
+    }
     
     // ______________________________ UPDATE INFO: ____________________________________
 
-    /// Updates full info
-    pub async fn update_info(&mut self) -> Result<()> {
-        self.update_devices_info().await?;
-        self.update_volume_info().await?;
-        
-        // info!("Media info updated!");
+    /// Updates full info, returns the change events
+    /// (the info is read without lock and skipped if it was changed locally meanwhile)
+    pub async fn update_info(&self) -> Result<Vec<MediaEvent>> {
+        let revision = self.info.lock().unwrap().revision;
+        let fresh = self.read_info().await?;
 
-        Ok(())
+        let mut info = self.info.lock().unwrap();
+        if info.revision != revision {
+            return Ok(vec![]);
+        }
+
+        let mut events = vec![];
+        if info.devices != fresh.devices { events.push(MediaEvent::DevicesChanged(fresh.devices.clone())); }
+        if info.active != fresh.active { events.push(MediaEvent::ActiveChanged(fresh.active.clone())); }
+        if info.volume != fresh.volume { events.push(MediaEvent::VolumeChanged(fresh.volume)); }
+        if info.audio_muted != fresh.audio_muted { events.push(MediaEvent::AudioMuteChanged(fresh.audio_muted)); }
+        if info.micro_muted != fresh.micro_muted { events.push(MediaEvent::MicroMuteChanged(fresh.micro_muted)); }
+
+        *info = MediaInfo { revision, ..fresh };
+
+        Ok(events)
     }
 
-    /// Updates media devices list
-    pub async fn update_devices_info(&mut self) -> Result<()> {
+    /// Reads full info (slow, runs the sound tools)
+    pub async fn read_info(&self) -> Result<MediaInfo> {
+        if self.dry_run {
+            return Ok(self.get_info());
+        }
+
         let (active, devices) = self.get_all_devices().await?;
 
-        self.devices = devices;
-        self.active = active;
+        let volume = match &active {
+            Some(device) => self.get_device_volume(&device.name).await?,
+            None => 0,
+        };
+        let audio_muted = match &active {
+            Some(device) => self.device_is_muted(&device.name).await?,
+            None => false,
+        };
+        let micro_muted = match devices.iter().find(|device| device.kind.is_micro_device() && device.is_active) {
+            Some(device) => self.device_is_muted(&device.name).await?,
+            None => false,
+        };
 
-        Ok(())
+        Ok(MediaInfo { devices, active, volume, audio_muted, micro_muted, revision: 0 })
     }
 
-    /// Updates media volume value
-    pub async fn update_volume_info(&mut self) -> Result<()> {
-        self.volume = self.get_audio_volume().await?;
-
-        Ok(())
+    /// Changes the info locally (marks it as newer than the running update)
+    fn modify<F: FnOnce(&mut MediaInfo)>(&self, f: F) {
+        let mut info = self.info.lock().unwrap();
+        f(&mut info);
+        info.revision += 1;
     }
 
     // ______________________________ FAST METHODS: ____________________________________
 
+    /// Returns the media info snapshot
+    pub fn get_info(&self) -> MediaInfo {
+        self.info.lock().unwrap().clone()
+    }
+
     /// Returns devices list
-    pub fn get_devices(&self) -> &Vec<Device> {
-        &self.devices
+    pub fn get_devices(&self) -> Vec<Device> {
+        self.info.lock().unwrap().devices.clone()
+    }
+
+    /// Returns current audio volume (0-100)
+    pub fn get_volume(&self) -> i32 {
+        self.info.lock().unwrap().volume
+    }
+
+    /// Returns current audio device
+    pub fn get_active(&self) -> Option<Device> {
+        self.info.lock().unwrap().active.clone()
     }
 
     /// Get audio devices list
     pub async fn get_audio_devices(&self) -> Result<Vec<Device>> {
-        Ok(self.devices.iter().filter(|device| device.kind.is_audio_device()).map(|device| device.clone()).collect::<Vec<_>>())
+        Ok(self.get_devices().into_iter().filter(|device| device.kind.is_audio_device()).collect::<Vec<_>>())
     }
 
     /// Get microphone devices list
     pub async fn get_micro_devices(&self) -> Result<Vec<Device>> {
-        Ok(self.devices.iter().filter(|device| device.kind.is_micro_device()).map(|device| device.clone()).collect::<Vec<_>>())
+        Ok(self.get_devices().into_iter().filter(|device| device.kind.is_micro_device()).collect::<Vec<_>>())
     }
 
     /// Get a current media device
@@ -139,7 +320,7 @@ impl Media {
     pub async fn get_all_devices(&self) -> Result<(Option<Device>, Vec<Device>)> {
         let output = Command::new(&self.svv_path)
             .arg("/scomma")
-            .output()
+            .exec_output(self.dry_run)
             .map_err(|_| Error::FailedReadDevicesList)?;
         
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -170,9 +351,8 @@ impl Media {
 
             if let Some(name) = record.get(0) {
                 // filter device by name:
-                if let Some(filter) = &self.device_filter {
-                    if !(filter)(&name) { continue }
-                }
+                let filter = if kind.is_audio_device() { &self.audio_filter }else{ &self.micro_filter };
+                if !filter.accepts(name) { continue }
                 
                 // skip empty names
                 if name.is_empty() { continue; }
@@ -200,40 +380,44 @@ impl Media {
         Ok((active, devices))
     }
 
-    /// Set active audio device
+    /// Set active audio device (by name or alias)
     pub async fn set_audio_device(&mut self, name: &str) -> Result<()> {
-        for device in &self.get_audio_devices().await? {
-            if device.name == name {
-                let status = Command::new(&self.svv_path)
-                    .arg("/SetDefault")
-                    .arg(name)
-                    .arg("all")  // all = Console, Multimedia, Communications
-                    .status()?;
-                
-                if status.success() {
-                    info!("Switched to '{}' audio device", name);
-                } else {
-                    err!("Failed to switch to '{}' audio device", name);
-                }
+        let devices = self.get_audio_devices().await?;
+        let device = self.audio_filter.find(&devices, name).ok_or_else(|| Error::DeviceNotFound(name.to_owned()))?;
+        let alias = self.audio_filter.alias(&device.name);
 
-                self.active = Some(device.clone());
+        let status = Command::new(&self.svv_path)
+            .arg("/SetDefault")
+            .arg(&device.name)
+            .arg("all")  // all = Console, Multimedia, Communications
+            .exec(self.dry_run)?;
+        
+        if status.success() {
+            info!("Switched to '{}' audio device", alias);
+        } else {
+            err!("Failed to switch to '{}' audio device", alias);
+        }
 
-                return Ok(());
+        self.modify(|info| {
+            for item in info.devices.iter_mut().filter(|item| item.kind.is_audio_device()) {
+                item.is_active = item.name == device.name;
             }
-        }
+            info.active = Some(Device { is_active: true, ..device.clone() });
+        });
 
-        Err(Error::DeviceNotFound(name.to_owned()).into())
+        Ok(())
     }
 
     /// Switch to next audio device
     pub async fn switch_next_audio_device(&mut self) -> Result<()> {
-        let devices = self.get_audio_devices().await?;
+        let devices = self.audio_filter.order(self.get_audio_devices().await?);
         if devices.is_empty() {
             return Err(Error::FoundNoDevices.into());
         }
 
-        let current_index = devices.iter().position(|d| d.is_active).unwrap_or(0);
-        let next_index = (current_index + 1) % devices.len();
+        let next_index = devices.iter().position(|d| d.is_active)
+            .map(|index| (index + 1) % devices.len())
+            .unwrap_or(0);
 
         self.set_audio_device(&devices[next_index].name).await?;
 
@@ -242,57 +426,57 @@ impl Media {
 
     /// Switch to previous audio device
     pub async fn switch_prev_audio_device(&mut self) -> Result<()> {
-        let devices = self.get_audio_devices().await?;
+        let devices = self.audio_filter.order(self.get_audio_devices().await?);
         if devices.is_empty() {
             return Err(Error::FoundNoDevices.into());
         }
 
-        let current_index = devices.iter().position(|d| d.is_active).unwrap_or(0);
-        let prev_index = if current_index == 0 {
-            devices.len() - 1
-        } else {
-            current_index - 1
-        };
+        let prev_index = devices.iter().position(|d| d.is_active)
+            .map(|index| (index + devices.len() - 1) % devices.len())
+            .unwrap_or(devices.len() - 1);
 
         self.set_audio_device(&devices[prev_index].name).await?;
 
         Ok(())
     }
 
-    /// Set active microphone device
+    /// Set active microphone device (by name or alias)
     pub async fn set_micro_device(&self, name: &str) -> Result<()> {
-        for device in &self.get_micro_devices().await? {
-            if device.name == name {
-                let status = Command::new(&self.svv_path)
-                    .arg("/SetDefault")
-                    .arg(name)
-                    .arg("all")  // all = Console, Multimedia, Communications
-                    .status()?;
-                
-                if status.success() {
-                    info!("Switched to '{}' microphone device", name);
-                } else {
-                    err!("Failed to switch to '{}' microphone device", name);
-                }
+        let devices = self.get_micro_devices().await?;
+        let device = self.micro_filter.find(&devices, name).ok_or_else(|| Error::DeviceNotFound(name.to_owned()))?;
+        let alias = self.micro_filter.alias(&device.name);
 
-                // self.active_micro = Some(device.clone());
+        let status = Command::new(&self.svv_path)
+            .arg("/SetDefault")
+            .arg(&device.name)
+            .arg("all")  // all = Console, Multimedia, Communications
+            .exec(self.dry_run)?;
+        
+        if status.success() {
+            info!("Switched to '{}' microphone device", alias);
+        } else {
+            err!("Failed to switch to '{}' microphone device", alias);
+        }
 
-                return Ok(());
+        self.modify(|info| {
+            for item in info.devices.iter_mut().filter(|item| item.kind.is_micro_device()) {
+                item.is_active = item.name == device.name;
             }
-        }
+        });
 
-        Err(Error::DeviceNotFound(name.to_owned()).into())
+        Ok(())
     }
 
     /// Switch to next microphone device
     pub async fn switch_next_micro_device(&mut self) -> Result<()> {
-        let devices = self.get_micro_devices().await?;
+        let devices = self.micro_filter.order(self.get_micro_devices().await?);
         if devices.is_empty() {
             return Err(Error::FoundNoDevices.into());
         }
 
-        let current_index = devices.iter().position(|d| d.is_active).unwrap_or(0);
-        let next_index = (current_index + 1) % devices.len();
+        let next_index = devices.iter().position(|d| d.is_active)
+            .map(|index| (index + 1) % devices.len())
+            .unwrap_or(0);
 
         self.set_micro_device(&devices[next_index].name).await?;
 
@@ -301,17 +485,14 @@ impl Media {
 
     /// Switch to previous microphone device
     pub async fn switch_prev_micro_device(&mut self) -> Result<()> {
-        let devices = self.get_micro_devices().await?;
+        let devices = self.micro_filter.order(self.get_micro_devices().await?);
         if devices.is_empty() {
             return Err(Error::FoundNoDevices.into());
         }
 
-        let current_index = devices.iter().position(|d| d.is_active).unwrap_or(0);
-        let prev_index = if current_index == 0 {
-            devices.len() - 1
-        } else {
-            current_index - 1
-        };
+        let prev_index = devices.iter().position(|d| d.is_active)
+            .map(|index| (index + devices.len() - 1) % devices.len())
+            .unwrap_or(devices.len() - 1);
 
         self.set_micro_device(&devices[prev_index].name).await?;
 
@@ -320,12 +501,17 @@ impl Media {
 
     /// Get current audio volume (0-100)
     pub async fn get_audio_volume(&self) -> Result<i32> {
-        let device = self.active.as_ref().ok_or(Error::ActiveDeviceNotFound)?;
+        let device = self.get_active().ok_or(Error::ActiveDeviceNotFound)?;
+
+        self.get_device_volume(&device.name).await
+    }
 
+    /// Get device volume (0-100)
+    pub async fn get_device_volume(&self, name: &str) -> Result<i32> {
         let status = Command::new(&self.svcl_path)
             .arg("/GetPercent")
-            .arg(&device.name)
-            .status()?;
+            .arg(name)
+            .exec(self.dry_run)?;
 
         let code = status.code().unwrap_or(0);
         
@@ -341,39 +527,40 @@ impl Media {
         let status = Command::new(&self.nircmd_path)
             .arg("setsysvolume")  // Исправленная команда!
             .arg(sys_volume.to_string())
-            .status()?;
+            .exec(self.dry_run)?;
         
         if status.success() {
-            self.volume = volume;
+            self.modify(|info| info.volume = volume);
         } else {
             return Err(Error::FailedSetVolume.into());
         }
         
-        Ok(self.volume)
+        Ok(volume)
     }
 
     /// Increase audio volume by delta
     pub async fn increase_audio_volume(&mut self, delta: i32) -> Result<i32> {
-        let new_volume = (self.volume + delta).min(100);
+        let new_volume = (self.get_volume() + delta).min(100);
         self.set_audio_volume(new_volume).await
     }
 
     /// Decrease audio volume by delta
     pub async fn decrease_audio_volume(&mut self, delta: i32) -> Result<i32> {
-        let new_volume = self.volume.saturating_sub(delta);
+        let new_volume = self.get_volume().saturating_sub(delta);
         self.set_audio_volume(new_volume).await
     }
 
     /// Toggle audio mute/unmute
     pub async fn switch_audio_mute(&self) -> Result<()> {
-        let device = self.active.as_ref().ok_or(Error::ActiveDeviceNotFound)?;
+        let device = self.get_active().ok_or(Error::ActiveDeviceNotFound)?;
 
         let status = Command::new(&self.svv_path)
             .arg("/Switch")
             .arg(&device.name)
-            .status()?;
+            .exec(self.dry_run)?;
         
         if status.success() {
+            self.modify(|info| info.audio_muted = !info.audio_muted);
             info!("Media device '{}' muted/unmuted", device.name);
         } else {
             err!("Failed to toggle mute");
@@ -388,9 +575,10 @@ impl Media {
         let status = Command::new(&self.svv_path)
             .arg("/Switch")
             .arg(&device.name)
-            .status()?;
+            .exec(self.dry_run)?;
         
         if status.success() {
+            self.modify(|info| info.micro_muted = !info.micro_muted);
             info!("Media device '{}' muted/unmuted", device.name);
         } else {
             err!("Failed to toggle microphone mute");
@@ -398,31 +586,269 @@ impl Media {
         Ok(())
     }
 
-    /// Check if audio device is muted
-    pub async fn audio_is_muted(&self) -> Result<bool> {
-        let device = self.active.as_ref().ok_or(Error::ActiveDeviceNotFound)?;
+    /// Set audio mute state
+    pub async fn set_audio_mute(&self, mute: bool) -> Result<()> {
+        let device = self.get_active().ok_or(Error::ActiveDeviceNotFound)?;
 
-        let status = Command::new(&self.svcl_path)
-            .arg("/GetMute")
+        let status = Command::new(&self.svv_path)
+            .arg(if mute { "/Mute" }else{ "/Unmute" })
             .arg(&device.name)
-            .status()?;
+            .exec(self.dry_run)?;
         
-        // Exit code: 1 = muted, 0 = not muted
-        let is_muted = status.code().map(|code| code == 1).unwrap_or(false);
-        Ok(is_muted)
+        if status.success() {
+            self.modify(|info| info.audio_muted = mute);
+            info!("Media device '{}' {}", device.name, if mute { "muted" }else{ "unmuted" });
+        } else {
+            err!("Failed to set audio mute");
+        }
+        Ok(())
+    }
+
+    /// Set microphone mute state
+    pub async fn set_micro_mute(&self, mute: bool) -> Result<()> {
+        let device = self.get_active_micro_device().await?;
+
+        let status = Command::new(&self.svv_path)
+            .arg(if mute { "/Mute" }else{ "/Unmute" })
+            .arg(&device.name)
+            .exec(self.dry_run)?;
+        
+        if status.success() {
+            self.modify(|info| info.micro_muted = mute);
+            info!("Media device '{}' {}", device.name, if mute { "muted" }else{ "unmuted" });
+        } else {
+            err!("Failed to set microphone mute");
+        }
+        Ok(())
+    }
+
+    /// Check if audio device is muted
+    pub async fn audio_is_muted(&self) -> Result<bool> {
+        let device = self.get_active().ok_or(Error::ActiveDeviceNotFound)?;
+
+        self.device_is_muted(&device.name).await
     }
 
     /// Check if microphone is muted
     pub async fn micro_is_muted(&self) -> Result<bool> {
         let device = self.get_active_micro_device().await?;
 
+        self.device_is_muted(&device.name).await
+    }
+
+    /// Check if device is muted
+    pub async fn device_is_muted(&self, name: &str) -> Result<bool> {
         let status = Command::new(&self.svcl_path)
             .arg("/GetMute")
-            .arg(&device.name)
-            .status()?;
+            .arg(name)
+            .exec(self.dry_run)?;
         
         // Exit code: 1 = muted, 0 = not muted
         let is_muted = status.code().map(|code| code == 1).unwrap_or(false);
         Ok(is_muted)
     }
+
+    // ______________________________ APP SESSIONS: ____________________________________
+
+    /// Gets application audio sessions list
+    pub async fn get_sessions(&self) -> Result<Vec<Session>> {
+        if self.dry_run {
+            return Ok(vec![]);
+        }
+
+        if cfg!(target_os = "linux") {
+            let output = Command::new("pactl")
+                .args(["-f", "json", "list", "sink-inputs"])
+                .exec_output(self.dry_run)
+                .map_err(|_| Error::FailedReadDevicesList)?;
+
+            return Session::parse_pactl(&String::from_utf8_lossy(&output.stdout));
+        }
+
+        let output = Command::new(&self.svcl_path)
+            .args(["/scomma", "", "/Columns", "Name,Type,Command-Line Friendly ID,Process Path,Process ID,Muted,Volume Percent"])
+            .exec_output(self.dry_run)
+            .map_err(|_| Error::FailedReadDevicesList)?;
+
+        Ok(Session::parse_svv(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Finds audio sessions of target application
+    pub async fn find_sessions(&self, target: &AppTarget) -> Result<Vec<Session>> {
+        let sessions = self.get_sessions().await?;
+
+        let found = match target {
+            AppTarget::Focused => {
+                let pid = get_focused_pid().ok_or_else(|| Error::SessionNotFound(target.to_string()))?;
+                sessions.into_iter().filter(|session| session.pid == Some(pid)).collect::<Vec<_>>()
+            }
+            AppTarget::Process(name) => sessions.into_iter().filter(|session| session.matches(name)).collect::<Vec<_>>(),
+        };
+
+        if found.is_empty() {
+            return Err(Error::SessionNotFound(target.to_string()).into());
+        }
+
+        Ok(found)
+    }
+
+    /// Set application volume (0-100)
+    pub async fn set_app_volume(&self, target: &AppTarget, volume: i32) -> Result<()> {
+        let volume = volume.clamp(0, 100);
+
+        if cfg!(target_os = "linux") {
+            for session in self.find_sessions(target).await? {
+                self.pactl(&["set-sink-input-volume", &session.id, &fmt!("{volume}%")])?;
+            }
+        } else {
+            self.svv(&["/SetVolume", &target.to_string(), &volume.to_string()])?;
+        }
+
+        info!("Set '{target}' application volume to {volume}%");
+        Ok(())
+    }
+
+    /// Change application volume by delta
+    pub async fn change_app_volume(&self, target: &AppTarget, delta: i32) -> Result<()> {
+        if cfg!(target_os = "linux") {
+            for session in self.find_sessions(target).await? {
+                self.pactl(&["set-sink-input-volume", "--", &session.id, &fmt!("{delta:+}%")])?;
+            }
+        } else {
+            self.svv(&["/ChangeVolume", &target.to_string(), &delta.to_string()])?;
+        }
+
+        info!("Changed '{target}' application volume by {delta:+}%");
+        Ok(())
+    }
+
+    /// Set application mute state (or toggle it)
+    pub async fn set_app_mute(&self, target: &AppTarget, mute: Option<bool>) -> Result<()> {
+        if cfg!(target_os = "linux") {
+            let state = match mute { Some(true) => "1", Some(false) => "0", None => "toggle" };
+
+            for session in self.find_sessions(target).await? {
+                self.pactl(&["set-sink-input-mute", &session.id, state])?;
+            }
+        } else {
+            let command = match mute { Some(true) => "/Mute", Some(false) => "/Unmute", None => "/Switch" };
+            self.svv(&[command, &target.to_string()])?;
+        }
+
+        match mute {
+            Some(true) => info!("Application '{target}' muted"),
+            Some(false) => info!("Application '{target}' unmuted"),
+            None => info!("Application '{target}' muted/unmuted"),
+        }
+        Ok(())
+    }
+
+    /// Set application output device (by name or alias)
+    pub async fn set_app_device(&self, target: &AppTarget, name: &str) -> Result<()> {
+        let devices = self.get_audio_devices().await?;
+        let device = self.audio_filter.find(&devices, name).map(|device| device.name.clone()).unwrap_or_else(|| name.to_owned());
+
+        if cfg!(target_os = "linux") {
+            let sessions = self.find_sessions(target).await?;
+            let sink = self.find_sink(&device)?;
+
+            for session in sessions {
+                self.pactl(&["move-sink-input", &session.id, &sink])?;
+            }
+        } else {
+            self.svv(&["/SetAppDefault", &device, "all", &target.to_string()])?;
+        }
+
+        info!("Routed '{target}' application to '{}' audio device", self.audio_filter.alias(&device));
+        Ok(())
+    }
+
+    /// Executes SoundVolumeView command
+    fn svv(&self, args: &[&str]) -> Result<()> {
+        let status = Command::new(&self.svv_path).args(args).exec(self.dry_run)?;
+
+        if !status.success() {
+            return Err(Error::FailedSessionCommand(args.join(" ")).into());
+        }
+        Ok(())
+    }
+
+    /// Finds the pactl sink name by device description (or sink name)
+    fn find_sink(&self, device: &str) -> Result<String> {
+        let output = Command::new("pactl")
+            .args(["-f", "json", "list", "sinks"])
+            .exec_output(self.dry_run)
+            .map_err(|_| Error::FailedReadDevicesList)?;
+
+        if self.dry_run {
+            return Ok(device.to_owned());
+        }
+
+        let sinks: Vec<Value> = serde_json::from_str(&String::from_utf8_lossy(&output.stdout))?;
+
+        sinks.iter()
+            .find(|sink| {
+                let field = |name: &str| sink[name].as_str().unwrap_or_default().to_owned();
+                field("description").eq_ignore_ascii_case(device) || field("name") == device
+            })
+            .and_then(|sink| sink["name"].as_str())
+            .map(str::to_owned)
+            .ok_or_else(|| Error::DeviceNotFound(device.to_owned()).into())
+    }
+
+    /// Executes pactl command
+    fn pactl(&self, args: &[&str]) -> Result<()> {
+        let status = Command::new("pactl").args(args).exec(self.dry_run)?;
+
+        if !status.success() {
+            return Err(Error::FailedSessionCommand(args.join(" ")).into());
+        }
+        Ok(())
+    }
+}
+
+/// Returns the focused window process ID (Linux, X11 only)
+fn get_focused_pid() -> Option<u32> {
+    let output = Command::new("xdotool")
+        .args(["getactivewindow", "getwindowpid"])
+        .output()
+        .ok()?;
+
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// The command execution (logs instead of running in dry-run mode)
+trait Exec {
+    fn exec(&mut self, dry_run: bool) -> std::io::Result<ExitStatus>;
+    fn exec_output(&mut self, dry_run: bool) -> std::io::Result<Output>;
+}
+
+impl Exec for Command {
+    fn exec(&mut self, dry_run: bool) -> std::io::Result<ExitStatus> {
+        if dry_run {
+            info!("[dry-run] {self:?}");
+            return Ok(success_status());
+        }
+
+        self.status()
+    }
+
+    fn exec_output(&mut self, dry_run: bool) -> std::io::Result<Output> {
+        if dry_run {
+            info!("[dry-run] {self:?}");
+            return Ok(Output { status: success_status(), stdout: vec![], stderr: vec![] });
+        }
+
+        self.output()
+    }
+}
+
+/// Returns the successful exit status
+fn success_status() -> ExitStatus {
+    #[cfg(unix)]
+    use std::os::unix::process::ExitStatusExt;
+    #[cfg(windows)]
+    use std::os::windows::process::ExitStatusExt;
+
+    ExitStatus::from_raw(0)
 }
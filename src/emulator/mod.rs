@@ -1,3 +1,6 @@
-pub mod media;      pub use media::{ Media, Device, DeviceKind };
+pub mod media;      pub use media::{ Media, MediaInfo, MediaEvent, Device, DeviceKind, DeviceFilter };
+pub mod poller;     pub use poller::{ MediaPoller, PollerHandle };
+pub mod session;    pub use session::{ Session, AppTarget };
 pub mod keyboard;   pub use keyboard::{ Keyboard, Key };
 pub mod mouse;      pub use mouse::{ Mouse };
+pub mod mpris;      pub use mpris::{ Mpris, NowPlaying };
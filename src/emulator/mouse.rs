@@ -4,7 +4,8 @@ use enigo::{ Axis, Button, Coordinate, Direction, Enigo, Mouse as EnigoMouse, Se
 
 #[derive(Debug, Clone)]
 pub struct Mouse {
-    enigo: Arc<Mutex<Enigo>>,
+    enigo: Option<Arc<Mutex<Enigo>>>,
+    held: Arc<Mutex<Vec<Button>>>,
 }
 
 impl Mouse {
@@ -13,32 +14,37 @@ impl Mouse {
         let settings = Settings::default();
         let enigo = Enigo::new(&settings).unwrap();
         Ok(Self {
-            enigo: Arc::new(Mutex::new(enigo)),
+            enigo: Some(Arc::new(Mutex::new(enigo))),
+            held: Arc::new(Mutex::new(vec![])),
         })
     }
 
+    /// Creates a dry-run mouse (logs actions instead of moving)
+    pub fn dry_run() -> Self {
+        Self {
+            enigo: None,
+            held: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
     /// Returns current mouse coordinates
     pub fn get_coords(&self) -> Result<(i32, i32)> {
-        let enigo = self.enigo.lock().map_err(|_| InputError::Simulate("Mutex poisoned"))?;
-        enigo.location().map_err(From::from)
+        self.simulate("get coords", |enigo| enigo.location().map_err(From::from))
     }
 
     /// Returns screen resolution (width, height)
     pub fn get_display_size(&self) -> Result<(i32, i32)> {
-        let enigo = self.enigo.lock().map_err(|_| InputError::Simulate("Mutex poisoned"))?;
-        enigo.main_display().map_err(From::from)
+        self.simulate("get display size", |enigo| enigo.main_display().map_err(From::from))
     }
 
     /// Move mouse horizontally (relative)
     pub fn move_x(&self, dx: i32) -> Result<()> {
-        let mut enigo = self.enigo.lock().map_err(|_| InputError::Simulate("Mutex poisoned"))?;
-        enigo.move_mouse(dx, 0, Coordinate::Rel).map_err(From::from)
+        self.simulate(fmt!("move x {dx}"), |enigo| enigo.move_mouse(dx, 0, Coordinate::Rel).map_err(From::from))
     }
 
     /// Move mouse vertically (relative)
     pub fn move_y(&self, dy: i32) -> Result<()> {
-        let mut enigo = self.enigo.lock().map_err(|_| InputError::Simulate("Mutex poisoned"))?;
-        enigo.move_mouse(0, dy, Coordinate::Rel).map_err(From::from)
+        self.simulate(fmt!("move y {dy}"), |enigo| enigo.move_mouse(0, dy, Coordinate::Rel).map_err(From::from))
     }
 
     /// Move mouse to center
@@ -47,57 +53,93 @@ impl Mouse {
         let center_x = width / 2;
         let center_y = height / 2;
         
-        let mut enigo = self.enigo.lock().map_err(|_| InputError::Simulate("Mutex poisoned"))?;
-        enigo.move_mouse(center_x, center_y, Coordinate::Abs).map_err(From::from)
+        self.simulate("move center", |enigo| enigo.move_mouse(center_x, center_y, Coordinate::Abs).map_err(From::from))
     }
 
     /// Press left mouse button
     pub fn press_left(&self, hold: bool) -> Result<()> {
-        let mut enigo = self.enigo.lock().map_err(|_| InputError::Simulate("Mutex poisoned"))?;
-
-        enigo.button(Button::Left, Direction::Press)?;
-
-        if !hold {
-            enigo.button(Button::Left, Direction::Release)?;
-        }
-
-        Ok(())
+        self.press(Button::Left, hold)
     }
 
     /// Release left mouse button
     pub fn release_left(&self) -> Result<()> {
-        let mut enigo = self.enigo.lock().map_err(|_| InputError::Simulate("Mutex poisoned"))?;
-        enigo.button(Button::Left, Direction::Release).map_err(From::from)
+        self.release(Button::Left)
     }
 
     /// Press right mouse button
     pub fn press_right(&self, hold: bool) -> Result<()> {
-        let mut enigo = self.enigo.lock().map_err(|_| InputError::Simulate("Mutex poisoned"))?;
-
-        enigo.button(Button::Right, Direction::Press)?;
-
-        if !hold {
-            enigo.button(Button::Right, Direction::Release)?;
-        }
-
-        Ok(())
+        self.press(Button::Right, hold)
     }
 
     /// Release right mouse button
     pub fn release_right(&self) -> Result<()> {
-        let mut enigo = self.enigo.lock().map_err(|_| InputError::Simulate("Mutex poisoned"))?;
-        enigo.button(Button::Right, Direction::Release).map_err(From::from)
+        self.release(Button::Right)
     }
 
     /// Scroll horizontally
     pub fn scroll_x(&self, delta: i32) -> Result<()> {
-        let mut enigo = self.enigo.lock().map_err(|_| InputError::Simulate("Mutex poisoned"))?;
-        enigo.scroll(delta, Axis::Horizontal).map_err(From::from)
+        self.simulate(fmt!("scroll x {delta}"), |enigo| enigo.scroll(delta, Axis::Horizontal).map_err(From::from))
     }
 
     /// Scroll vertically
     pub fn scroll_y(&self, delta: i32) -> Result<()> {
-        let mut enigo = self.enigo.lock().map_err(|_| InputError::Simulate("Mutex poisoned"))?;
-        enigo.scroll(delta, Axis::Vertical).map_err(From::from)
+        self.simulate(fmt!("scroll y {delta}"), |enigo| enigo.scroll(delta, Axis::Vertical).map_err(From::from))
+    }
+
+    /// Release all held mouse buttons
+    pub fn release_held(&self) -> Result<()> {
+        let buttons = self.held.lock().map_err(|_| InputError::Simulate("Mutex poisoned"))?.clone();
+
+        for button in buttons {
+            self.release(button)?;
+        }
+
+        Ok(())
+    }
+
+    /// Press mouse button
+    fn press(&self, button: Button, hold: bool) -> Result<()> {
+        self.simulate(fmt!("press {button:?}"), |enigo| {
+            enigo.button(button, Direction::Press)?;
+
+            if !hold {
+                enigo.button(button, Direction::Release)?;
+            }
+
+            Ok(())
+        })?;
+
+        if hold {
+            if let Ok(mut held) = self.held.lock() {
+                if !held.contains(&button) {
+                    held.push(button);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Release mouse button
+    fn release(&self, button: Button) -> Result<()> {
+        if let Ok(mut held) = self.held.lock() {
+            held.retain(|held| *held != button);
+        }
+
+        self.simulate(fmt!("release {button:?}"), |enigo| enigo.button(button, Direction::Release).map_err(From::from))
+    }
+
+    /// Simulates mouse action (or logs it in dry-run mode)
+    fn simulate<T: Default, S: AsRef<str>, F: FnOnce(&mut Enigo) -> Result<T>>(&self, label: S, f: F) -> Result<T> {
+        match &self.enigo {
+            Some(enigo) => {
+                let mut enigo = enigo.lock().map_err(|_| InputError::Simulate("Mutex poisoned"))?;
+                f(&mut enigo)
+            }
+            None => {
+                info!("[dry-run] mouse {}", label.as_ref());
+                Ok(T::default())
+            }
+        }
     }
 }
@@ -0,0 +1,277 @@
+use crate::prelude::*;
+use zbus::{ Connection, proxy::CacheProperties, fdo::DBusProxy };
+
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+
+/// The MPRIS player interface
+#[zbus::proxy(
+    interface = "org.mpris.MediaPlayer2.Player",
+    default_path = "/org/mpris/MediaPlayer2"
+)]
+trait Player {
+    fn play_pause(&self) -> zbus::Result<()>;
+    fn pause(&self) -> zbus::Result<()>;
+    fn stop(&self) -> zbus::Result<()>;
+    fn next(&self) -> zbus::Result<()>;
+    fn previous(&self) -> zbus::Result<()>;
+    fn seek(&self, offset: i64) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn playback_status(&self) -> zbus::Result<String>;
+
+    #[zbus(property)]
+    fn metadata(&self) -> zbus::Result<HashMap<String, zbus::zvariant::OwnedValue>>;
+}
+
+/// The now playing info
+#[derive(Debug, Clone, Default)]
+pub struct NowPlaying {
+    pub player: String,
+    pub status: String,
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: String,
+}
+
+impl ::std::fmt::Display for NowPlaying {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        write!(f, "'{}' by '{}' ({}, {})", self.title, self.artists.join(", "), self.status, self.player)
+    }
+}
+
+/// The MPRIS media controller (D-Bus)
+#[derive(Debug, Clone)]
+pub struct Mpris {
+    connection: Connection,
+    player: Option<String>,
+    last_active: Option<String>,
+}
+
+impl Mpris {
+    /// Connects to session bus (or to a bus by address)
+    pub async fn connect(address: Option<&str>, player: Option<String>) -> Result<Self> {
+        let connection = match address {
+            Some(address) => zbus::connection::Builder::address(address)?.build().await?,
+            None => Connection::session().await?,
+        };
+
+        Ok(Self::with_connection(connection, player))
+    }
+
+    /// Creates a controller on an existing bus connection
+    pub fn with_connection(connection: Connection, player: Option<String>) -> Self {
+        Self {
+            connection,
+            player,
+            last_active: None,
+        }
+    }
+
+    /// Returns bus names of all MPRIS players
+    pub async fn get_players(&self) -> Result<Vec<String>> {
+        let dbus = DBusProxy::new(&self.connection).await?;
+
+        Ok(dbus.list_names().await?
+            .into_iter()
+            .map(|name| name.to_string())
+            .filter(|name| name.starts_with(MPRIS_PREFIX))
+            .collect())
+    }
+
+    /// Finds the target player (configured one, a playing one or the last controlled)
+    pub async fn find_player(&mut self) -> Result<String> {
+        let mut players = self.get_players().await?;
+
+        // find configured player:
+        if let Some(target) = &self.player {
+            return players.into_iter()
+                .find(|name| name[MPRIS_PREFIX.len()..].to_lowercase().contains(&target.to_lowercase()))
+                .ok_or_else(|| Error::PlayerNotFound.into());
+        }
+
+        // check the last controlled player first:
+        if let Some(last) = &self.last_active {
+            players.sort_by_key(|name| name != last);
+        }
+
+        // find playing now player:
+        for name in &players {
+            if self.proxy(name).await?.playback_status().await.map(|status| status == "Playing").unwrap_or(false) {
+                self.last_active = Some(name.clone());
+                return Ok(name.clone());
+            }
+        }
+
+        // or the last controlled (sorted first) or any player:
+        players.into_iter().next().ok_or_else(|| Error::PlayerNotFound.into())
+    }
+
+    /// Creates a player proxy by bus name
+    async fn proxy<'a>(&self, name: &'a str) -> Result<PlayerProxy<'a>> {
+        Ok(PlayerProxy::builder(&self.connection)
+            .destination(name)?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?)
+    }
+
+    /// Switch media play/pause
+    pub async fn play_pause(&mut self) -> Result<()> {
+        let name = self.find_player().await?;
+        self.proxy(&name).await?.play_pause().await?;
+        self.last_active = Some(name);
+
+        Ok(())
+    }
+
+    /// Pause media
+    pub async fn pause(&mut self) -> Result<()> {
+        let name = self.find_player().await?;
+        self.proxy(&name).await?.pause().await?;
+
+        Ok(())
+    }
+
+    /// Stop media
+    pub async fn stop(&mut self) -> Result<()> {
+        let name = self.find_player().await?;
+        self.proxy(&name).await?.stop().await?;
+
+        Ok(())
+    }
+
+    /// Switch to next track
+    pub async fn next(&mut self) -> Result<()> {
+        let name = self.find_player().await?;
+        self.proxy(&name).await?.next().await?;
+        self.last_active = Some(name);
+
+        Ok(())
+    }
+
+    /// Switch to previous track
+    pub async fn previous(&mut self) -> Result<()> {
+        let name = self.find_player().await?;
+        self.proxy(&name).await?.previous().await?;
+        self.last_active = Some(name);
+
+        Ok(())
+    }
+
+    /// Seek track by seconds (negative value seeks backward)
+    pub async fn seek(&mut self, seconds: i64) -> Result<()> {
+        let name = self.find_player().await?;
+        self.proxy(&name).await?.seek(seconds * 1_000_000).await?;
+        self.last_active = Some(name);
+
+        Ok(())
+    }
+
+    /// Returns now playing track info
+    pub async fn now_playing(&mut self) -> Result<NowPlaying> {
+        let name = self.find_player().await?;
+        let proxy = self.proxy(&name).await?;
+
+        let status = proxy.playback_status().await?;
+        let mut metadata = proxy.metadata().await?;
+
+        Ok(NowPlaying {
+            player: name[MPRIS_PREFIX.len()..].to_owned(),
+            status,
+            title: metadata.remove("xesam:title").and_then(|value| String::try_from(value).ok()).unwrap_or_default(),
+            artists: metadata.remove("xesam:artist").and_then(|value| Vec::<String>::try_from(value).ok()).unwrap_or_default(),
+            album: metadata.remove("xesam:album").and_then(|value| String::try_from(value).ok()).unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zbus::zvariant::{ OwnedValue, Value as ZValue };
+
+    /// The fake bus daemon (lists the player name on a p2p connection)
+    struct FakeBus;
+
+    #[zbus::interface(name = "org.freedesktop.DBus")]
+    impl FakeBus {
+        fn list_names(&self) -> Vec<String> {
+            vec![str!("org.freedesktop.DBus"), fmt!("{MPRIS_PREFIX}test")]
+        }
+    }
+
+    /// The minimal MPRIS player (records the called methods)
+    struct FakePlayer {
+        calls: Arc<StdMutex<Vec<String>>>,
+    }
+
+    #[zbus::interface(name = "org.mpris.MediaPlayer2.Player")]
+    impl FakePlayer {
+        fn play_pause(&self) {
+            self.calls.lock().unwrap().push(str!("PlayPause"));
+        }
+
+        fn seek(&self, offset: i64) {
+            self.calls.lock().unwrap().push(fmt!("Seek({offset})"));
+        }
+
+        #[zbus(property)]
+        fn playback_status(&self) -> String {
+            str!("Playing")
+        }
+
+        #[zbus(property)]
+        fn metadata(&self) -> HashMap<String, OwnedValue> {
+            let value = |value: ZValue| OwnedValue::try_from(value).unwrap();
+
+            hash_map! {
+                str!("xesam:title") => value(ZValue::from("Song")),
+                str!("xesam:artist") => value(ZValue::from(vec!["Artist"])),
+                str!("xesam:album") => value(ZValue::from("Album")),
+            }
+        }
+    }
+
+    /// Serves the fake player on a private p2p connection
+    async fn connect(calls: Arc<StdMutex<Vec<String>>>) -> (Connection, Mpris) {
+        let (server, client) = tokio::net::UnixStream::pair().unwrap();
+
+        let server = zbus::connection::Builder::unix_stream(server)
+            .server(zbus::Guid::generate()).unwrap()
+            .p2p()
+            .serve_at("/org/freedesktop/DBus", FakeBus).unwrap()
+            .serve_at("/org/mpris/MediaPlayer2", FakePlayer { calls }).unwrap()
+            .build();
+        let client = zbus::connection::Builder::unix_stream(client)
+            .p2p()
+            .build();
+
+        let (server, client) = tokio::join!(server, client);
+
+        (server.unwrap(), Mpris::with_connection(client.unwrap(), None))
+    }
+
+    #[tokio::test]
+    async fn controls_player() {
+        let calls = Arc::new(StdMutex::new(vec![]));
+        let (_server, mut mpris) = connect(calls.clone()).await;
+
+        mpris.play_pause().await.unwrap();
+        mpris.seek(-5).await.unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), vec![str!("PlayPause"), str!("Seek(-5000000)")]);
+    }
+
+    #[tokio::test]
+    async fn reads_now_playing() {
+        let (_server, mut mpris) = connect(Arc::new(StdMutex::new(vec![]))).await;
+
+        let playing = mpris.now_playing().await.unwrap();
+
+        assert_eq!(playing.player, "test");
+        assert_eq!(playing.status, "Playing");
+        assert_eq!(playing.title, "Song");
+        assert_eq!(playing.artists, vec![str!("Artist")]);
+        assert_eq!(playing.album, "Album");
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{ prelude::*, Media, MediaEvent };
+use tokio::sync::{ watch, broadcast::{ self, Sender, Receiver } };
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+/// The background media state poller
+#[derive(Debug, Clone)]
+pub struct MediaPoller {
+    media: Media,
+    interval: Duration,
+    sender: Sender<MediaEvent>,
+}
+
+impl MediaPoller {
+    /// Creates a new media poller (the media info is shared with the controller)
+    pub fn new(media: Media, interval: Duration) -> Self {
+        let (sender, _) = broadcast::channel(32);
+
+        Self { media, interval, sender }
+    }
+
+    /// Subscribes to media state change events
+    pub fn subscribe(&self) -> Receiver<MediaEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Starts refreshing media info in background (the sound tools run outside the input loop)
+    pub fn spawn(self) -> PollerHandle {
+        let (stop, mut stopped) = watch::channel(false);
+
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = stopped.changed() => break,
+                }
+
+                // the sound tools are blocking:
+                let media = self.media.clone();
+                let handle = tokio::runtime::Handle::current();
+
+                match tokio::task::spawn_blocking(move || handle.block_on(media.update_info())).await {
+                    Ok(Ok(events)) => for event in events {
+                        let _ = self.sender.send(event);
+                    },
+                    Ok(Err(e)) => err!("Error with updating media info: {e}"),
+                    Err(e) => err!("Error with updating media info: {e}"),
+                }
+            }
+        });
+
+        PollerHandle { stop, task, listeners: vec![] }
+    }
+}
+
+/// The running media poller (stops it and its events listeners)
+#[derive(Debug)]
+pub struct PollerHandle {
+    stop: watch::Sender<bool>,
+    task: JoinHandle<()>,
+    listeners: Vec<JoinHandle<()>>,
+}
+
+impl PollerHandle {
+    /// Adds an events listener task (it must end once the events channel is closed)
+    pub fn with_listener(mut self, listener: JoinHandle<()>) -> Self {
+        self.listeners.push(listener);
+        self
+    }
+
+    /// Stops polling and waits for the running refresh and listeners to finish
+    pub async fn stop(self) {
+        let _ = self.stop.send(true);
+        let _ = self.task.await;
+
+        // the events channel is closed with the poller:
+        for listener in self.listeners {
+            let _ = listener.await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ DeviceConfig, DeviceFilter };
+
+    #[tokio::test]
+    async fn stops_polling() {
+        let filter = || DeviceFilter::new(&DeviceConfig::default()).unwrap();
+        let poller = MediaPoller::new(Media::dry_run(filter(), filter()), Duration::from_millis(10));
+
+        let mut events = poller.subscribe();
+        let listener = tokio::spawn(async move { while events.recv().await.is_ok() {} });
+        let handle = poller.spawn().with_listener(listener);
+
+        sleep(Duration::from_millis(30)).await;
+
+        tokio::time::timeout(Duration::from_secs(1), handle.stop()).await
+            .expect("poller isn't stopped");
+    }
+
+    #[test]
+    fn runtime_exits_after_stop() {
+        let (done, finished) = std::sync::mpsc::channel();
+
+        // the runtime drop waits for blocking tasks (a process with a running poller never exits):
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+
+            runtime.block_on(async {
+                let filter = || DeviceFilter::new(&DeviceConfig::default()).unwrap();
+                let handle = MediaPoller::new(Media::dry_run(filter(), filter()), Duration::from_millis(10)).spawn();
+
+                sleep(Duration::from_millis(30)).await;
+                handle.stop().await;
+            });
+
+            drop(runtime);
+            let _ = done.send(());
+        });
+
+        finished.recv_timeout(Duration::from_secs(5)).expect("runtime isn't dropped");
+    }
+}
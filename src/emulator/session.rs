@@ -0,0 +1,129 @@
+use crate::prelude::*;
+use csv::Reader;
+
+/// The application audio session
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub id: String,
+    pub name: String,
+    pub pid: Option<u32>,
+    pub volume: i32,
+    pub is_muted: bool,
+}
+
+impl Session {
+    /// Checks the session belongs to process (by name or PID)
+    pub fn matches(&self, process: &str) -> bool {
+        let process = process.trim_end_matches(".exe");
+
+        self.name.trim_end_matches(".exe").eq_ignore_ascii_case(process)
+        || self.pid.map(|pid| pid.to_string() == process).unwrap_or(false)
+    }
+
+    /// Parses sessions from SoundVolumeView CSV output (application items only)
+    pub fn parse_svv(output: &str) -> Vec<Self> {
+        let mut reader = Reader::from_reader(output.trim_start_matches('\u{feff}').as_bytes());
+
+        let headers = match reader.headers() {
+            Ok(headers) => headers.clone(),
+            Err(e) => {
+                err!("CSV parsing error: {}", e);
+                return vec![];
+            }
+        };
+        let column = |name: &str| headers.iter().position(|header| header == name);
+        let (type_col, id_col, path_col, pid_col, muted_col, volume_col) = (
+            column("Type"), column("Command-Line Friendly ID"), column("Process Path"),
+            column("Process ID"), column("Muted"), column("Volume Percent"),
+        );
+
+        let mut sessions = vec![];
+
+        for result in reader.records() {
+            let record = match result {
+                Ok(r) => r,
+                Err(e) => {
+                    err!("CSV parsing error: {}", e);
+                    continue;
+                }
+            };
+            let get = |col: Option<usize>| col.and_then(|col| record.get(col)).unwrap_or_default();
+
+            if get(type_col) != "Application" { continue }
+
+            let path = get(path_col);
+            let name = Path::new(path).file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+
+            // skip system sounds:
+            if name.is_empty() { continue }
+
+            sessions.push(Self {
+                id: get(id_col).to_owned(),
+                name,
+                pid: get(pid_col).parse().ok(),
+                volume: get(volume_col).trim_end_matches('%').parse::<f32>().map(|v| v.round() as i32).unwrap_or(0),
+                is_muted: get(muted_col) == "Yes",
+            });
+        }
+
+        sessions
+    }
+
+    /// Parses sessions from 'pactl -f json list sink-inputs' output
+    pub fn parse_pactl(output: &str) -> Result<Vec<Self>> {
+        let inputs: Vec<Value> = serde_json::from_str(output)?;
+
+        Ok(inputs.iter()
+            .map(|input| {
+                let prop = |name: &str| input["properties"][name].as_str().unwrap_or_default().to_owned();
+                let name = Some(prop("application.process.binary")).filter(|s| !s.is_empty()).unwrap_or_else(|| prop("application.name"));
+
+                // average volume of all channels:
+                let volumes = input["volume"].as_object()
+                    .map(|channels| channels.values()
+                        .filter_map(|channel| channel["value_percent"].as_str()?.trim_end_matches('%').parse::<i32>().ok())
+                        .collect::<Vec<_>>()
+                    )
+                    .unwrap_or_default();
+
+                Self {
+                    id: input["index"].as_u64().unwrap_or_default().to_string(),
+                    name,
+                    pid: prop("application.process.id").parse().ok(),
+                    volume: if volumes.is_empty() { 0 }else{ volumes.iter().sum::<i32>() / volumes.len() as i32 },
+                    is_muted: input["mute"].as_bool().unwrap_or(false),
+                }
+            })
+            .collect())
+    }
+}
+
+/// The application sessions target (focused window app or named process)
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum AppTarget {
+    #[default]
+    Focused,
+    Process(String),
+}
+
+impl From<String> for AppTarget {
+    fn from(value: String) -> Self {
+        if value == "focused" { Self::Focused }else{ Self::Process(value) }
+    }
+}
+
+impl From<AppTarget> for String {
+    fn from(value: AppTarget) -> Self {
+        value.to_string()
+    }
+}
+
+impl ::std::fmt::Display for AppTarget {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        match self {
+            Self::Focused => write!(f, "focused"),
+            Self::Process(name) => write!(f, "{name}"),
+        }
+    }
+}
@@ -20,6 +20,66 @@ pub enum Error {
     #[display = "The path contains invalid UTF-8 characters"]
     InvalidPath,
 
+    #[display = "All input ports are closed"]
+    NoInputPorts,
+
+    #[display = "Unknown handler with name '{0}'"]
+    UnknownHandler(String),
+
+    #[display = "Invalid '{0}' handler args: {1}"]
+    InvalidHandlerArgs(String, String),
+
+    #[display = "Invalid bind '{0}': {1}"]
+    InvalidBind(String, String),
+
+    #[display = "Found no bind named as '{0}'"]
+    BindNotFound(String),
+
+    #[display = "Control request failed: {0}"]
+    ControlError(String),
+
+    #[display = "The dispatcher is stopped"]
+    DispatcherStopped,
+
+    #[display = "Another instance is already running (PID {0})"]
+    AlreadyRunning(u32),
+
+    #[display = "Couldn't find the user home directory"]
+    HomeNotFound,
+
+    #[display = "The simulator requires pseudo-terminals (Unix only), use a virtual COM port pair instead"]
+    SimulatorUnsupported,
+
+    #[display = "Unknown simulator command '{0}'"]
+    UnknownSimCommand(String),
+
+    #[display = "Invalid LIRC config (line {0}): {1}"]
+    InvalidLirc(usize, String),
+
+    #[display = "LIRC remote '{0}' can't be recognized (it has no NEC address shared by all codes and no 'pre_data' prefix)"]
+    UnrecognizableLirc(String),
+
+    #[display = "Remote '{0}' already exists"]
+    RemoteExists(String),
+
+    #[display = "Remote '{0}' isn't found"]
+    RemoteNotFound(String),
+
+    #[display = "The web UI must listen on a loopback address, got '{0}'"]
+    WebUiNotLocal(String),
+
+    #[display = "Unsupported bundle version {0} (expected {1} or lower)"]
+    UnsupportedBundle(u32, u32),
+
+    #[display = "Invalid bundle script name '{0}'"]
+    InvalidBundleScript(String),
+
+    #[display = "Script '{0}' error: {1}"]
+    ScriptError(String, String),
+
+    #[display = "Invalid pattern '{0}': {1}"]
+    InvalidPattern(String, String),
+
     #[display = "No audio devices set"]
     FoundNoDevices,
 
@@ -34,4 +94,22 @@ pub enum Error {
 
     #[display = "Failed to change audio volume"]
     FailedSetVolume,
+
+    #[display = "Found no audio session of '{0}' application"]
+    SessionNotFound(String),
+
+    #[display = "Failed to execute audio session command '{0}'"]
+    FailedSessionCommand(String),
+
+    #[display = "Power operation '{0}' isn't supported on this platform"]
+    UnsupportedPowerAction(String),
+
+    #[display = "Failed to execute power operation '{0}'"]
+    FailedPowerAction(String),
+
+    #[display = "Found no MPRIS media player"]
+    PlayerNotFound,
+
+    #[display = "MPRIS media control isn't available"]
+    MprisUnavailable,
 }
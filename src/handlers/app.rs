@@ -0,0 +1,51 @@
+use crate::{ prelude::*, handlers::{ Handler, Context, args::{ self, AppVolumeArgs, AppArgs, AppDeviceArgs } } };
+use async_trait::async_trait;
+
+/// Changes application volume (by delta or to value)
+pub struct AppVolume;
+
+#[async_trait]
+impl Handler for AppVolume {
+    fn validate(&self, args: &Value) -> Result<()> {
+        args::parse::<AppVolumeArgs>(args).map(drop)
+    }
+
+    async fn call(&self, ctx: &mut Context<'_>, args: &Value) -> Result<()> {
+        let media = &ctx.devices.media;
+
+        match args::parse(args)? {
+            AppVolumeArgs::Delta { app, delta } => media.change_app_volume(&app, delta).await,
+            AppVolumeArgs::Value { app, value } => media.set_app_volume(&app, value).await,
+        }
+    }
+}
+
+/// Mutes, unmutes or toggles application sound
+pub struct AppMute(pub Option<bool>);
+
+#[async_trait]
+impl Handler for AppMute {
+    fn validate(&self, args: &Value) -> Result<()> {
+        args::parse::<AppArgs>(args).map(drop)
+    }
+
+    async fn call(&self, ctx: &mut Context<'_>, args: &Value) -> Result<()> {
+        let AppArgs { app } = args::parse(args)?;
+        ctx.devices.media.set_app_mute(&app, self.0).await
+    }
+}
+
+/// Routes application sound to audio device
+pub struct AppDevice;
+
+#[async_trait]
+impl Handler for AppDevice {
+    fn validate(&self, args: &Value) -> Result<()> {
+        args::parse::<AppDeviceArgs>(args).map(drop)
+    }
+
+    async fn call(&self, ctx: &mut Context<'_>, args: &Value) -> Result<()> {
+        let AppDeviceArgs { app, device } = args::parse(args)?;
+        ctx.devices.media.set_app_device(&app, &device).await
+    }
+}
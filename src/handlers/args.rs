@@ -0,0 +1,78 @@
+use crate::{ prelude::*, AppTarget };
+use serde::de::DeserializeOwned;
+
+/// The 'volume' handler args
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum VolumeArgs {
+    Delta { delta: i32 },
+    Value { value: i32 },
+}
+
+/// The 'scroll' handler args
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScrollArgs {
+    pub delta: i32,
+    #[serde(default)]
+    pub horizontal: bool,
+}
+
+/// The 'seek' handler args
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeekArgs {
+    pub seconds: i64,
+}
+
+/// The 'set-audio-device' and 'set-micro-device' handler args
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeviceArgs {
+    pub name: String,
+}
+
+/// The 'app-volume' handler args
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum AppVolumeArgs {
+    Delta {
+        #[serde(default)]
+        app: AppTarget,
+        delta: i32,
+    },
+    Value {
+        #[serde(default)]
+        app: AppTarget,
+        value: i32,
+    },
+}
+
+/// The 'app-mute', 'app-unmute' and 'app-mute-toggle' handler args
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AppArgs {
+    #[serde(default)]
+    pub app: AppTarget,
+}
+
+/// The 'app-device' handler args
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AppDeviceArgs {
+    #[serde(default)]
+    pub app: AppTarget,
+    pub device: String,
+}
+
+/// The handler without args
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NoArgs {}
+
+/// Parses handler args (missing args are parsed as empty object)
+pub fn parse<T: DeserializeOwned>(args: &Value) -> Result<T> {
+    let args = if args.is_null() { Value::Object(Default::default()) }else{ args.clone() };
+
+    Ok(serde_json::from_value(args)?)
+}
@@ -0,0 +1,133 @@
+use crate::{ prelude::*, handlers::{ Handler, Context, args::{ self, VolumeArgs, DeviceArgs } } };
+use async_trait::async_trait;
+
+/// Switches to next audio device
+pub struct SwitchAudio;
+
+#[async_trait]
+impl Handler for SwitchAudio {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        ctx.devices.media.switch_next_audio_device().await
+    }
+}
+
+/// Sets active audio device by name
+pub struct SetAudioDevice;
+
+#[async_trait]
+impl Handler for SetAudioDevice {
+    fn validate(&self, args: &Value) -> Result<()> {
+        args::parse::<DeviceArgs>(args).map(drop)
+    }
+
+    async fn call(&self, ctx: &mut Context<'_>, args: &Value) -> Result<()> {
+        let DeviceArgs { name } = args::parse(args)?;
+        ctx.devices.media.set_audio_device(&name).await
+    }
+}
+
+/// Sets active microphone device by name
+pub struct SetMicroDevice;
+
+#[async_trait]
+impl Handler for SetMicroDevice {
+    fn validate(&self, args: &Value) -> Result<()> {
+        args::parse::<DeviceArgs>(args).map(drop)
+    }
+
+    async fn call(&self, ctx: &mut Context<'_>, args: &Value) -> Result<()> {
+        let DeviceArgs { name } = args::parse(args)?;
+        ctx.devices.media.set_micro_device(&name).await
+    }
+}
+
+/// Changes audio volume (by delta or to value)
+pub struct Volume;
+
+#[async_trait]
+impl Handler for Volume {
+    fn validate(&self, args: &Value) -> Result<()> {
+        args::parse::<VolumeArgs>(args).map(drop)
+    }
+
+    async fn call(&self, ctx: &mut Context<'_>, args: &Value) -> Result<()> {
+        let media = &mut ctx.devices.media;
+
+        let volume = match args::parse(args)? {
+            VolumeArgs::Delta { delta } if delta < 0 => media.decrease_audio_volume(-delta).await?,
+            VolumeArgs::Delta { delta } => media.increase_audio_volume(delta).await?,
+            VolumeArgs::Value { value } => media.set_audio_volume(value.clamp(0, 100)).await?,
+        };
+        info!("Set audio volume to {volume}%");
+
+        Ok(())
+    }
+}
+
+/// Toggles audio mute (microphone follows the audio mute state)
+pub struct MuteUnmute;
+
+#[async_trait]
+impl Handler for MuteUnmute {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        let media = &ctx.devices.media;
+
+        media.switch_audio_mute().await?;
+        media.set_micro_mute(media.audio_is_muted().await?).await?;
+
+        Ok(())
+    }
+}
+
+/// The microphone mute operation
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MicroMute {
+    Mute,
+    Unmute,
+    Toggle,
+}
+
+/// Mutes, unmutes or toggles microphone
+pub struct Micro(pub MicroMute);
+
+#[async_trait]
+impl Handler for Micro {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        let media = &ctx.devices.media;
+
+        match self.0 {
+            MicroMute::Mute => media.set_micro_mute(true).await,
+            MicroMute::Unmute => media.set_micro_mute(false).await,
+            MicroMute::Toggle => media.switch_micro_mute().await,
+        }
+    }
+}
+
+/// Switches to next microphone device
+pub struct SwitchMicro;
+
+#[async_trait]
+impl Handler for SwitchMicro {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        ctx.devices.media.switch_next_micro_device().await
+    }
+}
+
+/// Unmutes microphone while the bind is repeated (mutes it after release)
+pub struct PushToTalk;
+
+#[async_trait]
+impl Handler for PushToTalk {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        let release = Duration::from_millis(ctx.config.push_to_talk_release_ms);
+
+        if ctx.state.push_to_talk.is_none() {
+            ctx.devices.media.set_micro_mute(false).await?;
+            info!("Push-to-talk started");
+        }
+
+        ctx.state.push_to_talk = Some(Instant::now() + release);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{ prelude::*, Snapshot, handlers::{ Handler, Context } };
+use async_trait::async_trait;
+
+/// Restores the state before the last bind change (active devices, volume, mute and mode)
+pub struct Undo;
+
+#[async_trait]
+impl Handler for Undo {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        let Some(snapshot) = ctx.state.history.pop() else {
+            info!("Nothing to undo");
+            return Ok(());
+        };
+
+        // keep the state to retry if it failed to restore:
+        if let Err(e) = Self::restore(ctx, &snapshot).await {
+            ctx.state.history.push_back(snapshot);
+            return Err(e);
+        }
+
+        info!("Undone the last change ({} left)", ctx.state.history.len());
+        Ok(())
+    }
+}
+
+impl Undo {
+    /// Restores the remembered state
+    async fn restore(ctx: &mut Context<'_>, snapshot: &Snapshot) -> Result<()> {
+        let media = &mut ctx.devices.media;
+        let current = Snapshot::capture(media, ctx.state.mode);
+
+        // restore devices first (volume and mute are applied to the restored device):
+        if let Some(name) = snapshot.audio_device.as_ref().filter(|name| current.audio_device.as_ref() != Some(*name)) {
+            media.set_audio_device(name).await?;
+        }
+        if let Some(name) = snapshot.micro_device.as_ref().filter(|name| current.micro_device.as_ref() != Some(*name)) {
+            media.set_micro_device(name).await?;
+        }
+
+        if snapshot.volume != current.volume {
+            media.set_audio_volume(snapshot.volume).await?;
+        }
+        if snapshot.audio_muted != current.audio_muted {
+            media.set_audio_mute(snapshot.audio_muted).await?;
+        }
+        if snapshot.micro_muted != current.micro_muted {
+            media.set_micro_mute(snapshot.micro_muted).await?;
+        }
+
+        if snapshot.mode != current.mode {
+            ctx.state.mode = snapshot.mode;
+            info!("Switched to {} mode", snapshot.mode.to_string().to_lowercase());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{ prelude::*, Key, Keyboard, Mpris, handlers::{ Handler, Context, args::{ self, SeekArgs } } };
+use async_trait::async_trait;
+
+/// Sends media command via MPRIS (or emulates media key if it's unavailable)
+pub async fn media_command(mpris: &mut Option<Mpris>, keyboard: &mut Keyboard, key: Key) -> Result<()> {
+    if let Some(mpris) = mpris {
+        let result = match key {
+            Key::MediaNextTrack => mpris.next().await,
+            Key::MediaPrevTrack => mpris.previous().await,
+            Key::MediaStop => mpris.stop().await,
+            _ => mpris.play_pause().await,
+        };
+
+        match result {
+            Ok(_) => return Ok(()),
+            Err(e) => err!("MPRIS error: {e}, using media key.."),
+        }
+    }
+
+    keyboard.press(&key, false).await
+}
+
+/// Sends a media command (play/pause, stop, next or previous track)
+pub struct MediaControl(pub Key);
+
+#[async_trait]
+impl Handler for MediaControl {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        let devices = &mut *ctx.devices;
+        media_command(&mut devices.mpris, &mut devices.keyboard, self.0.clone()).await?;
+
+        match self.0 {
+            Key::MediaStop => info!("Stopped media"),
+            Key::MediaNextTrack => info!("Switched to next track"),
+            Key::MediaPrevTrack => info!("Switched to previos track"),
+            _ => info!("Switched media play/pause"),
+        }
+
+        Ok(())
+    }
+}
+
+/// Seeks track by configured seconds (forward or backward)
+pub struct Seek(pub i64);
+
+#[async_trait]
+impl Handler for Seek {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        let mpris = ctx.devices.mpris.as_mut().ok_or(Error::MprisUnavailable)?;
+        let seconds = ctx.config.mpris.seek_seconds * self.0;
+
+        mpris.seek(seconds).await?;
+        info!("Seek track by {seconds}s");
+
+        Ok(())
+    }
+}
+
+/// Seeks track by seconds from args
+pub struct SeekBy;
+
+#[async_trait]
+impl Handler for SeekBy {
+    fn validate(&self, args: &Value) -> Result<()> {
+        args::parse::<SeekArgs>(args).map(drop)
+    }
+
+    async fn call(&self, ctx: &mut Context<'_>, args: &Value) -> Result<()> {
+        let SeekArgs { seconds } = args::parse(args)?;
+        let mpris = ctx.devices.mpris.as_mut().ok_or(Error::MprisUnavailable)?;
+
+        mpris.seek(seconds).await?;
+        info!("Seek track by {seconds}s");
+
+        Ok(())
+    }
+}
+
+/// Logs now playing track info
+pub struct NowPlayingInfo;
+
+#[async_trait]
+impl Handler for NowPlayingInfo {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        let mpris = ctx.devices.mpris.as_mut().ok_or(Error::MprisUnavailable)?;
+        info!("Now playing: {}", mpris.now_playing().await?);
+
+        Ok(())
+    }
+}
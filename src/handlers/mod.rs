@@ -0,0 +1,149 @@
+pub mod args;
+pub mod audio;      pub use audio::{ SwitchAudio, SetAudioDevice, SetMicroDevice, Volume, MuteUnmute, Micro, MicroMute, SwitchMicro, PushToTalk };
+pub mod app;        pub use app::{ AppVolume, AppMute, AppDevice };
+pub mod navigation; pub use navigation::{ Navigation, Navigate, Direction };
+pub mod media;      pub use media::{ MediaControl, Seek, SeekBy, NowPlayingInfo, media_command };
+pub mod mouse;      pub use mouse::{ ScrollStep, Scroll };
+pub mod power;      pub use power::PowerHandler;
+pub mod timer;      pub use timer::{ SleepTimerExtend, SleepTimerCancel };
+pub mod history;    pub use history::Undo;
+
+use crate::{ prelude::*, Action, Config, Trigger, Media, Keyboard, Mouse, Power, Mpris, PowerAction, Key, State };
+use async_trait::async_trait;
+
+/// The bind handler
+#[async_trait]
+pub trait Handler: Send + Sync {
+    /// Checks handler args (on config load)
+    fn validate(&self, args: &Value) -> Result<()> {
+        args::parse::<args::NoArgs>(args).map(drop)
+    }
+
+    /// Executes the handler
+    async fn call(&self, ctx: &mut Context<'_>, args: &Value) -> Result<()>;
+}
+
+/// The controlled devices
+pub struct Devices {
+    pub media: Media,
+    pub keyboard: Keyboard,
+    pub mouse: Mouse,
+    pub power: Power,
+    pub mpris: Option<Mpris>,
+    pub dry_run: bool,
+}
+
+/// The handler execution context
+pub struct Context<'a> {
+    pub devices: &'a mut Devices,
+    pub state: &'a mut State,
+    pub config: &'a Config,
+    pub trigger: &'a Trigger,
+}
+
+/// The handlers registry
+#[derive(Clone, Default)]
+pub struct Registry {
+    handlers: HashMap<String, Arc<dyn Handler>>,
+}
+
+impl Registry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self { handlers: HashMap::new() }
+    }
+
+    /// Creates a registry with built-in handlers
+    pub fn with_builtins() -> Self {
+        let mut this = Self::new();
+
+        this.register("switch-audio", SwitchAudio)
+            .register("set-audio-device", SetAudioDevice)
+            .register("set-micro-device", SetMicroDevice)
+            .register("volume", Volume)
+            .register("mute-unmute", MuteUnmute)
+            .register("mic-mute", Micro(MicroMute::Mute))
+            .register("mic-unmute", Micro(MicroMute::Unmute))
+            .register("mic-toggle", Micro(MicroMute::Toggle))
+            .register("mic-switch", SwitchMicro)
+            .register("push-to-talk", PushToTalk)
+
+            .register("app-volume", AppVolume)
+            .register("app-mute", AppMute(Some(true)))
+            .register("app-unmute", AppMute(Some(false)))
+            .register("app-mute-toggle", AppMute(None))
+            .register("app-device", AppDevice)
+
+            .register("navigation", Navigation)
+            .register("nav-left", Navigate(Direction::Left))
+            .register("nav-right", Navigate(Direction::Right))
+            .register("nav-up", Navigate(Direction::Up))
+            .register("nav-down", Navigate(Direction::Down))
+            .register("nav-center", Navigate(Direction::Center))
+
+            .register("media-play-pause", MediaControl(Key::MediaPlayPause))
+            .register("media-stop", MediaControl(Key::MediaStop))
+            .register("media-next", MediaControl(Key::MediaNextTrack))
+            .register("media-prev", MediaControl(Key::MediaPrevTrack))
+            .register("seek-forward", Seek(1))
+            .register("seek-backward", Seek(-1))
+            .register("seek", SeekBy)
+            .register("now-playing", NowPlayingInfo)
+
+            .register("scroll-up", ScrollStep(-1))
+            .register("scroll-down", ScrollStep(1))
+            .register("scroll", Scroll)
+
+            .register("sleep-mode", PowerHandler(PowerAction::Suspend))
+            .register("hibernate", PowerHandler(PowerAction::Hibernate))
+            .register("shutdown", PowerHandler(PowerAction::Shutdown))
+            .register("reboot", PowerHandler(PowerAction::Reboot))
+            .register("lock", PowerHandler(PowerAction::Lock))
+            .register("screen-off", PowerHandler(PowerAction::ScreenOff))
+
+            .register("sleep-timer", SleepTimerExtend)
+            .register("sleep-timer-cancel", SleepTimerCancel)
+
+            .register("undo", Undo);
+
+        this
+    }
+
+    /// Registers a handler by name (replaces the existing one)
+    pub fn register<H: Handler + 'static>(&mut self, name: &str, handler: H) -> &mut Self {
+        self.handlers.insert(name.to_owned(), Arc::new(handler));
+        self
+    }
+
+    /// Returns a handler by name
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Handler>> {
+        self.handlers.get(name).cloned()
+    }
+
+    /// Returns sorted handler names
+    pub fn names(&self) -> Vec<String> {
+        let mut names = self.handlers.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Checks the action handler exists and its args match the schema
+    pub fn validate(&self, action: &Action) -> Result<()> {
+        let Action::Handler { handler, args } = action else { return Ok(()) };
+
+        let handler_fn = self.get(handler).ok_or_else(|| Error::UnknownHandler(handler.to_owned()))?;
+
+        handler_fn.validate(args)
+            .map_err(|e| Error::InvalidHandlerArgs(handler.to_owned(), e.to_string()).into())
+    }
+
+    /// Checks all config bind actions
+    pub fn validate_config(&self, config: &Config) -> Result<()> {
+        for (label, action) in config.actions() {
+            self.validate(action)
+                .map_err(|e| Error::InvalidBind(label, e.to_string()))?;
+        }
+
+        Ok(())
+    }
+}
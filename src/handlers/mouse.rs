@@ -0,0 +1,46 @@
+use crate::{ prelude::*, handlers::{ Handler, Context, args::{ self, ScrollArgs } } };
+use async_trait::async_trait;
+
+const SCROLL_STEPS: (i32, i32) = (2, 5);
+
+/// Scrolls vertically by step (up or down)
+pub struct ScrollStep(pub i32);
+
+#[async_trait]
+impl Handler for ScrollStep {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        let step: i32 = ctx.trigger.step(SCROLL_STEPS);
+        ctx.devices.mouse.scroll_y(step * self.0)?;
+
+        if self.0 < 0 {
+            info!("Scroll up by {step}px");
+        } else {
+            info!("Scroll down by {step}px");
+        }
+
+        Ok(())
+    }
+}
+
+/// Scrolls by delta from args
+pub struct Scroll;
+
+#[async_trait]
+impl Handler for Scroll {
+    fn validate(&self, args: &Value) -> Result<()> {
+        args::parse::<ScrollArgs>(args).map(drop)
+    }
+
+    async fn call(&self, ctx: &mut Context<'_>, args: &Value) -> Result<()> {
+        let ScrollArgs { delta, horizontal } = args::parse(args)?;
+
+        if horizontal {
+            ctx.devices.mouse.scroll_x(delta)?;
+        } else {
+            ctx.devices.mouse.scroll_y(delta)?;
+        }
+        info!("Scroll by {delta}px");
+
+        Ok(())
+    }
+}
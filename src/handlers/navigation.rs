@@ -0,0 +1,113 @@
+use crate::{ prelude::*, Key, Mode, handlers::{ Handler, Context, media_command } };
+use async_trait::async_trait;
+
+const MOUSE_STEPS: (i32, i32) = (30, 100);
+const VOLUME_STEPS: (i32, i32) = (2, 5);
+
+/// Switches to next navigation mode
+pub struct Navigation;
+
+#[async_trait]
+impl Handler for Navigation {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        ctx.state.mode = ctx.state.mode.next();
+
+        match ctx.state.mode {
+            Mode::Media => info!("Switched to media mode"),
+            Mode::Mouse => info!("Switched to mouse emulation mode"),
+            Mode::Selector => info!("Switched to selector mode"),
+        }
+
+        Ok(())
+    }
+}
+
+/// The navigation direction
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+    Center,
+}
+
+/// Navigates by current mode (volume & tracks, mouse or selector)
+pub struct Navigate(pub Direction);
+
+#[async_trait]
+impl Handler for Navigate {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        let trigger = ctx.trigger;
+        let devices = &mut *ctx.devices;
+
+        match (ctx.state.mode, self.0) {
+            (Mode::Media, Direction::Left) => {
+                let volume = devices.media.decrease_audio_volume(trigger.step(VOLUME_STEPS)).await?;
+                info!("Set audio volume to {volume}%");
+            }
+            (Mode::Media, Direction::Right) => {
+                let volume = devices.media.increase_audio_volume(trigger.step(VOLUME_STEPS)).await?;
+                info!("Set audio volume to {volume}%");
+            }
+            (Mode::Media, Direction::Up) => {
+                if !trigger.is_repeated() {
+                    media_command(&mut devices.mpris, &mut devices.keyboard, Key::MediaNextTrack).await?;
+                    info!("Switched to next track");
+                }
+            }
+            (Mode::Media, Direction::Down) => {
+                if !trigger.is_repeated() {
+                    media_command(&mut devices.mpris, &mut devices.keyboard, Key::MediaPrevTrack).await?;
+                    info!("Switched to previos track");
+                }
+            }
+            (Mode::Media, Direction::Center) => {
+                media_command(&mut devices.mpris, &mut devices.keyboard, Key::MediaPlayPause).await?;
+                info!("Switched media play/pause");
+            }
+
+            (Mode::Mouse, Direction::Left) => {
+                let step: i32 = trigger.step(MOUSE_STEPS);
+                devices.mouse.move_x(-step)?;
+                info!("Move mouse left by {step}px");
+            }
+            (Mode::Mouse, Direction::Right) => {
+                let step: i32 = trigger.step(MOUSE_STEPS);
+                devices.mouse.move_x(step)?;
+                info!("Move mouse right by {step}px");
+            }
+            (Mode::Mouse, Direction::Up) => {
+                let step: i32 = trigger.step(MOUSE_STEPS);
+                devices.mouse.move_y(-step)?;
+                info!("Move mouse top by {step}px");
+            }
+            (Mode::Mouse, Direction::Down) => {
+                let step: i32 = trigger.step(MOUSE_STEPS);
+                devices.mouse.move_y(step)?;
+                info!("Move mouse bottom by {step}px");
+            }
+            (Mode::Mouse, Direction::Center) => {
+                devices.mouse.press_left(false)?;
+                info!("Pressed left mouse button");
+            }
+
+            (Mode::Selector, Direction::Left | Direction::Up) => {
+                devices.keyboard.press_all(&[Key::Shift, Key::Tab], true).await?;
+                sleep(Duration::from_millis(100)).await;
+                devices.keyboard.release_all(&[Key::Shift, Key::Tab]).await?;
+                info!("Selected previous element");
+            }
+            (Mode::Selector, Direction::Right | Direction::Down) => {
+                devices.keyboard.press(&Key::Tab, true).await?;
+                info!("Selected next element");
+            }
+            (Mode::Selector, Direction::Center) => {
+                devices.keyboard.press(&Key::Enter, false).await?;
+                info!("Pressed enter button");
+            }
+        }
+
+        Ok(())
+    }
+}
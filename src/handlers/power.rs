@@ -0,0 +1,23 @@
+use crate::{ prelude::*, PowerAction, handlers::{ Handler, Context } };
+use async_trait::async_trait;
+
+/// Executes a power operation
+pub struct PowerHandler(pub PowerAction);
+
+#[async_trait]
+impl Handler for PowerHandler {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        ctx.devices.power.execute(self.0)?;
+
+        match self.0 {
+            PowerAction::Suspend => info!("PC switched to sleep mode"),
+            PowerAction::Hibernate => info!("PC switched to hibernation"),
+            PowerAction::Shutdown => info!("PC is shutting down"),
+            PowerAction::Reboot => info!("PC is rebooting"),
+            PowerAction::Lock => info!("User session locked"),
+            PowerAction::ScreenOff => info!("Screen turned off"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use crate::{ prelude::*, handlers::{ Handler, Context } };
+use async_trait::async_trait;
+
+/// Starts/extends the sleep timer (cancels it after reaching the limit)
+pub struct SleepTimerExtend;
+
+#[async_trait]
+impl Handler for SleepTimerExtend {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        let timer_cfg = &ctx.config.sleep_timer;
+        let timer = &mut ctx.state.sleep_timer;
+
+        // cancel the timer after reaching the limit:
+        if timer.remaining().map(|remaining| remaining + timer_cfg.step() > timer_cfg.max()).unwrap_or(false) {
+            timer.cancel();
+            info!("Sleep timer cancelled");
+        } else {
+            timer.extend(timer_cfg.step(), timer_cfg.max());
+            info!("Sleep timer: {}", timer.state());
+        }
+
+        Ok(())
+    }
+}
+
+/// Cancels the sleep timer
+pub struct SleepTimerCancel;
+
+#[async_trait]
+impl Handler for SleepTimerCancel {
+    async fn call(&self, ctx: &mut Context<'_>, _args: &Value) -> Result<()> {
+        if ctx.state.sleep_timer.cancel() {
+            info!("Sleep timer cancelled");
+        }
+
+        Ok(())
+    }
+}
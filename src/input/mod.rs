@@ -0,0 +1,10 @@
+pub mod serial;     pub use serial::SerialReader;
+pub mod record;     pub use record::{ Recorder, Replayer, ReplaySpeed, RecordedEvent };
+pub mod simulator;  pub use simulator::{ Simulator, SimCommand };
+
+/// The raw input event
+#[derive(Debug, Clone)]
+pub struct InputEvent {
+    pub source: String,
+    pub line: String,
+}
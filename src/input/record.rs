@@ -0,0 +1,112 @@
+use crate::{ prelude::*, input::InputEvent };
+use std::fs::{ self, File };
+use std::io::{ BufWriter, Write };
+use tokio::sync::mpsc::Sender;
+use tokio::task::JoinHandle;
+
+/// The recorded input line (with monotonic time since the record start)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    pub time_ms: u64,
+    pub source: String,
+    pub line: String,
+}
+
+/// The raw input recorder (writes JSON lines)
+#[derive(Debug)]
+pub struct Recorder {
+    writer: BufWriter<File>,
+    start: Instant,
+}
+
+impl Recorder {
+    /// Creates a new record file
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+        info!("Recording input to '{}'..", path.as_ref().display());
+
+        Ok(Self {
+            writer: BufWriter::new(File::create(path)?),
+            start: Instant::now(),
+        })
+    }
+
+    /// Writes an input event
+    pub fn record(&mut self, event: &InputEvent) -> Result<()> {
+        let record = RecordedEvent {
+            time_ms: self.start.elapsed().as_millis() as u64,
+            source: event.source.clone(),
+            line: event.line.clone(),
+        };
+
+        serde_json::to_writer(&mut self.writer, &record)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// The replay speed
+///
+/// Only the original timing is faithful: the dispatcher gates repeats by the wall clock,
+/// so a sped up replay drops repeats (and a slowed down one adds them).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    Original,
+    Scaled(f64),
+    Fast,
+}
+
+/// The recorded input replayer
+#[derive(Debug, Clone)]
+pub struct Replayer {
+    events: Vec<RecordedEvent>,
+    speed: ReplaySpeed,
+}
+
+impl Replayer {
+    /// Reads a record file
+    pub fn open<P: AsRef<Path>>(path: P, speed: ReplaySpeed) -> Result<Self> {
+        let events = fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<StdResult<Vec<RecordedEvent>, _>>()?;
+
+        Ok(Self { events, speed })
+    }
+
+    /// Returns the recorded events count
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Checks the record is empty
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Starts sending recorded events in background (the sender is dropped at the end)
+    pub fn spawn(self, sender: Sender<InputEvent>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let start = tokio::time::Instant::now();
+
+            for record in self.events {
+                let delay = match self.speed {
+                    ReplaySpeed::Original => Some(Duration::from_millis(record.time_ms)),
+                    ReplaySpeed::Scaled(scale) => Some(Duration::from_millis(record.time_ms).div_f64(scale)),
+                    ReplaySpeed::Fast => None,
+                };
+
+                if let Some(delay) = delay {
+                    tokio::time::sleep_until(start + delay).await;
+                }
+
+                let event = InputEvent { source: record.source, line: record.line };
+                if sender.send(event).await.is_err() { break }
+            }
+
+            info!("Replay finished");
+        })
+    }
+}
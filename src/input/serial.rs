@@ -0,0 +1,72 @@
+use crate::{ prelude::*, PortConfig, input::InputEvent };
+use std::io::{ BufReader, BufRead };
+use tokio::sync::mpsc::Sender;
+use tokio::task::JoinHandle;
+
+/// The serial port reader
+#[derive(Debug, Clone)]
+pub struct SerialReader {
+    config: PortConfig,
+    reconnect_interval: Duration,
+}
+
+impl SerialReader {
+    /// Creates a new serial port reader
+    pub fn new(config: PortConfig) -> Self {
+        Self {
+            config,
+            reconnect_interval: Duration::from_secs(3),
+        }
+    }
+
+    /// Starts reading port lines in background (reconnects on port errors)
+    pub fn spawn(self, sender: Sender<InputEvent>) -> JoinHandle<()> {
+        tokio::task::spawn_blocking(move || {
+            loop {
+                if let Err(e) = self.read(&sender) {
+                    err!("Error with reading '{}' port: {e}", self.config.port);
+                }
+
+                // the dispatcher is stopped:
+                if sender.is_closed() { break }
+
+                std::thread::sleep(self.reconnect_interval);
+                info!("Reconnecting to '{}' port..", self.config.port);
+            }
+        })
+    }
+
+    /// Reads port lines until an error
+    fn read(&self, sender: &Sender<InputEvent>) -> Result<()> {
+        let port = serialport::new(&self.config.port, self.config.baud_rate)
+            .timeout(Duration::from_millis(10))
+            .open()?;
+
+        info!("Reading '{}' port as '{}' source..", self.config.port, self.config.name);
+
+        let mut reader = BufReader::new(port);
+        let mut line = String::new();
+
+        loop {
+            match reader.read_line(&mut line) {
+                Ok(0) => continue,
+                Ok(_) => {
+                    let event = InputEvent {
+                        source: self.config.name.clone(),
+                        line: line.trim().to_owned(),
+                    };
+                    line.clear();
+
+                    if sender.blocking_send(event).is_err() {
+                        return Ok(());
+                    }
+                }
+                // keeps a partially received line:
+                Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => {
+                    if sender.is_closed() { return Ok(()) }
+                }
+                Err(e) => return Err(e.into())
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{ prelude::*, binds::REPEAT_CODE };
+use std::fs::File;
+use std::io::{ self, BufRead, BufReader, Write };
+
+/// The NEC repeat frames interval
+const REPEAT_INTERVAL: Duration = Duration::from_millis(108);
+
+/// The simulator command (one per prompt or script line)
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SimCommand {
+    Send(String),
+    Repeat { count: u32, interval: Duration },
+    Hold { line: String, duration: Duration },
+    Wait(Duration),
+    Binds,
+    Quit,
+}
+
+impl SimCommand {
+    /// Parses a command line (bind name, hex code, 'repeat [count] [ms]', 'hold <bind|code> <ms>', 'wait <ms>', 'binds', 'quit')
+    pub fn parse(line: &str, config: &Config) -> Result<Option<Self>> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') { return Ok(None) }
+
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let number = |index: usize, default: u64| -> Result<u64> {
+            match words.get(index) {
+                Some(word) => word.parse().map_err(|_| Error::UnknownSimCommand(line.to_owned()).into()),
+                None => Ok(default),
+            }
+        };
+
+        Ok(Some(match words[0] {
+            "quit" | "exit" => Self::Quit,
+            "binds" => Self::Binds,
+            "wait" => Self::Wait(Duration::from_millis(number(1, 0)?)),
+            "repeat" => Self::Repeat {
+                count: number(1, 1)? as u32,
+                interval: Duration::from_millis(number(2, REPEAT_INTERVAL.as_millis() as u64)?),
+            },
+            "hold" if words.len() == 3 => Self::Hold {
+                line: Self::code_line(words[1], config).ok_or_else(|| Error::UnknownSimCommand(line.to_owned()))?,
+                duration: Duration::from_millis(number(2, 0)?),
+            },
+            _ => Self::Send(Self::code_line(line, config).ok_or_else(|| Error::UnknownSimCommand(line.to_owned()))?),
+        }))
+    }
+
+    /// Returns the receiver line by bind name or hex code ('FF38C7' or 'NEC FF38C7')
+    fn code_line(text: &str, config: &Config) -> Option<String> {
+        if let Some(code) = config.find_code(text) {
+            return Some(code.to_string());
+        }
+
+        let is_hex = |word: &str| {
+            let word = word.trim_start_matches("0x");
+            !word.is_empty() && word.chars().all(|c| c.is_ascii_hexdigit())
+        };
+
+        match text.split_whitespace().collect::<Vec<_>>()[..] {
+            [value] if is_hex(value) => Some(value.to_uppercase()),
+            [protocol, value] if is_hex(value) => Some(fmt!("{} {}", protocol.to_uppercase(), value.to_uppercase())),
+            _ => None,
+        }
+    }
+}
+
+/// The virtual Arduino (writes receiver lines to a pseudo-terminal)
+pub struct Simulator {
+    master: File,
+    path: PathBuf,
+    config: Config,
+    _slave: File,
+}
+
+impl Simulator {
+    /// Creates a PTY pair (the daemon reads its slave side)
+    #[cfg(unix)]
+    pub fn open(config: Config) -> Result<Self> {
+        use nix::pty::openpty;
+        use nix::sys::termios::{ tcgetattr, tcsetattr, cfmakeraw, SetArg };
+        use nix::unistd::ttyname;
+
+        let pty = openpty(None, None)?;
+
+        // disable echo and line editing:
+        let mut termios = tcgetattr(&pty.slave)?;
+        cfmakeraw(&mut termios);
+        tcsetattr(&pty.slave, SetArg::TCSANOW, &termios)?;
+
+        let path = ttyname(&pty.slave)?;
+
+        Ok(Self {
+            master: File::from(pty.master),
+            path,
+            config,
+            _slave: File::from(pty.slave),
+        })
+    }
+
+    /// Creates a PTY pair (isn't supported on this platform)
+    #[cfg(not(unix))]
+    pub fn open(_config: Config) -> Result<Self> {
+        Err(Error::SimulatorUnsupported.into())
+    }
+
+    /// Returns the slave PTY path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes a receiver line
+    pub fn send(&mut self, line: &str) -> Result<()> {
+        write!(self.master, "{line}\r\n")?;
+        self.master.flush()?;
+
+        println!("-> {line}");
+        Ok(())
+    }
+
+    /// Executes a command (returns false to quit)
+    pub fn execute(&mut self, command: SimCommand) -> Result<bool> {
+        match command {
+            SimCommand::Send(line) => self.send(&line)?,
+            SimCommand::Repeat { count, interval } => {
+                for _ in 0..count {
+                    std::thread::sleep(interval);
+                    self.send(REPEAT_CODE)?;
+                }
+            }
+            SimCommand::Hold { line, duration } => {
+                self.send(&line)?;
+
+                let start = Instant::now();
+                while start.elapsed() + REPEAT_INTERVAL <= duration {
+                    std::thread::sleep(REPEAT_INTERVAL);
+                    self.send(REPEAT_CODE)?;
+                }
+            }
+            SimCommand::Wait(duration) => std::thread::sleep(duration),
+            SimCommand::Binds => println!("{}", self.config.bind_names().join("\n")),
+            SimCommand::Quit => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    /// Executes commands from reader (prints a prompt in interactive mode)
+    pub fn run<R: BufRead>(&mut self, reader: R, interactive: bool) -> Result<()> {
+        let prompt = || {
+            if interactive {
+                print!("> ");
+                let _ = io::stdout().flush();
+            }
+        };
+
+        prompt();
+
+        for line in reader.lines() {
+            let line = line?;
+
+            match SimCommand::parse(&line, &self.config) {
+                Ok(Some(command)) => if !self.execute(command)? { break },
+                Ok(None) => {},
+                Err(e) if interactive => eprintln!("{e}"),
+                Err(e) => return Err(e),
+            }
+
+            prompt();
+        }
+
+        Ok(())
+    }
+
+    /// Executes commands from script file
+    pub fn run_script<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let file = File::open(path)?;
+
+        self.run(BufReader::new(file), false)
+    }
+}
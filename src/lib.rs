@@ -1,13 +1,29 @@
 pub mod error;      pub use error::{ StdResult, Result, Error };
 pub mod logger;     pub use logger::Logger;
-pub mod config;     pub use config::Config;
+pub mod config;     pub use config::{ Config, PortConfig, DeviceConfig, Pattern, CycleEntry, TimerConfig, MprisConfig, NotifyConfig };
 pub mod prelude;    use prelude::*;
 
 pub static LOGGER: Lazy<Logger> = Lazy::new(|| Logger::new());
 pub static CONFIG: Lazy<Arc<Mutex<Config>>> = Lazy::new(|| Config::new("config.json").unwrap_or_default());
 
-pub mod emulator;   pub use emulator::{ Media, Device, DeviceKind, Keyboard, Key, Mouse };
-pub mod binds;      pub use binds::{ Bind, Action };
+pub mod emulator;   pub use emulator::{ Media, MediaInfo, MediaEvent, MediaPoller, PollerHandle, Device, DeviceKind, DeviceFilter, Session, AppTarget, Keyboard, Key, Mouse, Mpris, NowPlaying };
+pub mod binds;      pub use binds::{ Bind, Action, Repeat, RepeatPolicy, Trigger, Code, Remote, Resolved };
+pub mod input;      pub use input::{ InputEvent, SerialReader, Recorder, Replayer, ReplaySpeed, Simulator, SimCommand };
+pub mod timer;      pub use timer::{ SleepTimer, TimerState };
+pub mod power;      pub use power::{ Power, PowerAction };
+pub mod notifier;   pub use notifier::{ Notifier, Notification, NotifyKind };
+pub mod state;      pub use state::{ State, Mode, Snapshot, History };
+pub mod handlers;   pub use handlers::{ Handler, Context, Devices, Registry };
+pub mod script;     pub use script::{ ScriptEngine, ScriptCommand, ScriptEnv };
+pub mod dispatcher; pub use dispatcher::{ Dispatcher, DispatchEvent };
+pub mod control;    pub use control::{ ControlServer, ControlClient, ControlRequest, ControlResponse, ControlMessage };
+pub mod service;    pub use service::PidFile;
+pub mod schema;     pub use schema::{ config_schema, key_names };
+pub mod lirc;       pub use lirc::LircRemote;
+pub mod bundle;     pub use bundle::{ Bundle, Conflict };
+pub mod tui;        pub use tui::Dashboard;
+pub mod web;        pub use web::WebUi;
+pub mod cli;        pub use cli::{ Cli, Command, RunArgs, CtlCommand };
 
 /// Generates path by program root path 
 pub fn root_path<P: AsRef<Path>>(relative_path: P) -> Result<PathBuf> {
@@ -0,0 +1,320 @@
+use crate::{ prelude::*, Action, Bind, Key, Remote, Repeat };
+use std::fs;
+
+/// The remote parsed from LIRC config ('lircd.conf')
+#[derive(Debug, Clone, Default)]
+pub struct LircRemote {
+    pub name: String,
+    pub bits: u32,
+    pub pre_data_bits: u32,
+    pub pre_data: u64,
+    pub post_data_bits: u32,
+    pub post_data: u64,
+    pub header: Option<(u32, u32)>,
+    pub codes: Vec<(String, u64)>,
+}
+
+impl LircRemote {
+    /// Reads remotes from LIRC config file
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Vec<Self>> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Parses remotes from LIRC config (raw codes remotes aren't supported)
+    pub fn parse(text: &str) -> Result<Vec<Self>> {
+        let mut remotes = vec![];
+        let mut remote: Option<Self> = None;
+        let mut in_codes = false;
+
+        for (index, line) in text.lines().enumerate() {
+            let number = index + 1;
+            let invalid = |message: &str| Error::InvalidLirc(number, message.to_owned());
+
+            // remove comments:
+            let line = line.split('#').next().unwrap_or_default();
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            let Some(&first) = words.first() else { continue };
+
+            match (first, words.get(1).copied()) {
+                ("begin", Some("remote")) => remote = Some(Self::default()),
+                ("end", Some("remote")) => {
+                    let parsed = remote.take().ok_or_else(|| invalid("unexpected 'end remote'"))?;
+                    if parsed.codes.is_empty() {
+                        return Err(Error::InvalidLirc(number, fmt!("remote '{}' has no codes", parsed.name)).into());
+                    }
+                    if parsed.total_bits() > 64 {
+                        return Err(Error::InvalidLirc(number, fmt!("remote '{}' codes are longer than 64 bits", parsed.name)).into());
+                    }
+
+                    remotes.push(parsed);
+                }
+                ("begin", Some("codes")) => in_codes = true,
+                ("end", Some("codes")) => in_codes = false,
+                ("begin", Some("raw_codes")) => return Err(invalid("raw codes aren't supported").into()),
+
+                _ => {
+                    let remote = remote.as_mut().ok_or_else(|| invalid("value outside of remote section"))?;
+                    let value = |index: usize| -> Result<u64> {
+                        let word = words.get(index).ok_or_else(|| invalid("missing value"))?;
+                        parse_number(word).ok_or_else(|| invalid(&fmt!("invalid number '{word}'")).into())
+                    };
+                    let bits = |index: usize| -> Result<u32> {
+                        let bits = value(index)?;
+                        if bits > 64 { return Err(invalid(&fmt!("bits count {bits} is more than 64")).into()) }
+
+                        Ok(bits as u32)
+                    };
+
+                    if in_codes {
+                        remote.codes.push((first.to_owned(), value(1)?));
+                        continue;
+                    }
+
+                    match first {
+                        "name" => remote.name = words.get(1).map(|name| name.to_string()).unwrap_or_default(),
+                        "bits" => remote.bits = bits(1)?,
+                        "pre_data_bits" => remote.pre_data_bits = bits(1)?,
+                        "pre_data" => remote.pre_data = value(1)?,
+                        "post_data_bits" => remote.post_data_bits = bits(1)?,
+                        "post_data" => remote.post_data = value(1)?,
+                        "header" => remote.header = Some((value(1)? as u32, value(2)? as u32)),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        Ok(remotes)
+    }
+
+    /// Returns the total code length (in bits)
+    pub fn total_bits(&self) -> u32 {
+        self.pre_data_bits + self.bits + self.post_data_bits
+    }
+
+    /// Returns the full code value (as the receiver decodes it, the parts shifted out of 64 bits are dropped)
+    pub fn code_value(&self, code: u64) -> u64 {
+        let value = self.pre_data.checked_shl(self.bits).unwrap_or(0) | code;
+
+        value.checked_shl(self.post_data_bits).unwrap_or(0) | self.post_data
+    }
+
+    /// Returns the code hex string (as the sketch prints it)
+    pub fn code_hex(&self, code: u64) -> String {
+        fmt!("{:X}", self.code_value(code))
+    }
+
+    /// Checks the remote uses NEC protocol (9 ms header and 32 bits codes)
+    pub fn is_nec(&self) -> bool {
+        let Some((pulse, space)) = self.header else { return false };
+
+        self.total_bits() == 32 && (8000..=10000).contains(&pulse) && (4000..=5000).contains(&space)
+    }
+
+    /// Returns the NEC address shared by all codes
+    pub fn address(&self) -> Option<String> {
+        if !self.is_nec() { return None }
+
+        let mut addresses = self.codes.iter().map(|(_, code)| (self.code_value(*code) >> 16) as u16);
+        let first = addresses.next()?;
+
+        addresses.all(|address| address == first).then(|| fmt!("{first:04X}"))
+    }
+
+    /// Returns the code hex prefix shared by all codes (the hex digits made of 'pre_data' only)
+    pub fn prefix(&self) -> Option<String> {
+        if self.pre_data == 0 { return None }
+
+        // the lower digits contain code bits:
+        let full = self.code_hex(0);
+        let code_digits = (self.bits + self.post_data_bits).div_ceil(4) as usize;
+
+        Some(full[..full.len().saturating_sub(code_digits)].to_owned()).filter(|prefix| !prefix.is_empty())
+    }
+
+    /// Converts to the remote config (binds are named after LIRC buttons, the remote is matched by NEC address or code prefix)
+    pub fn to_remote(&self, suggest: bool) -> Result<Remote> {
+        let address = self.address();
+        let prefix = if address.is_none() { self.prefix() } else { None };
+
+        if address.is_none() && prefix.is_none() {
+            return Err(Error::UnrecognizableLirc(self.name.clone()).into());
+        }
+
+        let binds = self.codes.iter()
+            .map(|(button, code)| {
+                let (action, repeat) = if suggest {
+                    suggest_action(button).unwrap_or_default()
+                } else {
+                    Default::default()
+                };
+
+                let bind = Bind {
+                    name: bind_name(button),
+                    action,
+                    repeat,
+                };
+
+                (self.code_hex(*code), bind)
+            })
+            .collect();
+
+        Ok(Remote {
+            protocol: self.is_nec().then(|| str!("NEC")),
+            address,
+            prefix,
+            binds,
+        })
+    }
+}
+
+/// Parses a decimal or hex ('0x..') number
+fn parse_number(word: &str) -> Option<u64> {
+    match word.strip_prefix("0x").or_else(|| word.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => word.parse().ok(),
+    }
+}
+
+/// Converts LIRC button name to bind name ('KEY_VOLUMEUP' -> 'volumeup')
+pub fn bind_name(button: &str) -> String {
+    button.trim_start_matches("KEY_").to_lowercase().replace('_', "-")
+}
+
+/// Suggests an action for LIRC button (by the standard key names)
+pub fn suggest_action(button: &str) -> Option<(Action, Repeat)> {
+    let handler = |name: &str| Action::Handler { handler: name.to_owned(), args: Value::Null };
+    let volume = |delta: i32| Action::Handler { handler: str!("volume"), args: serde_json::json!({ "delta": delta }) };
+
+    Some(match button.to_uppercase().as_str() {
+        "KEY_VOLUMEUP" => (volume(5), Repeat::Flag(true)),
+        "KEY_VOLUMEDOWN" => (volume(-5), Repeat::Flag(true)),
+        "KEY_MUTE" => (handler("mute-unmute"), Repeat::Flag(false)),
+        "KEY_PLAY" | "KEY_PAUSE" | "KEY_PLAYPAUSE" => (handler("media-play-pause"), Repeat::Flag(false)),
+        "KEY_STOP" => (handler("media-stop"), Repeat::Flag(false)),
+        "KEY_NEXT" | "KEY_NEXTSONG" => (handler("media-next"), Repeat::Flag(false)),
+        "KEY_PREVIOUS" | "KEY_PREVIOUSSONG" => (handler("media-prev"), Repeat::Flag(false)),
+        "KEY_FASTFORWARD" | "KEY_FORWARD" => (handler("seek-forward"), Repeat::Flag(true)),
+        "KEY_REWIND" | "KEY_BACKWARD" => (handler("seek-backward"), Repeat::Flag(true)),
+        "KEY_UP" => (handler("nav-up"), Repeat::Flag(true)),
+        "KEY_DOWN" => (handler("nav-down"), Repeat::Flag(true)),
+        "KEY_LEFT" => (handler("nav-left"), Repeat::Flag(true)),
+        "KEY_RIGHT" => (handler("nav-right"), Repeat::Flag(true)),
+        "KEY_OK" | "KEY_ENTER" | "KEY_SELECT" => (handler("nav-center"), Repeat::Flag(false)),
+        "KEY_MENU" => (handler("navigation"), Repeat::Flag(false)),
+        "KEY_CHANNELUP" | "KEY_PAGEUP" => (handler("scroll-up"), Repeat::Flag(true)),
+        "KEY_CHANNELDOWN" | "KEY_PAGEDOWN" => (handler("scroll-down"), Repeat::Flag(true)),
+        "KEY_POWER" | "KEY_SLEEP" => (handler("sleep-mode"), Repeat::Flag(false)),
+        "KEY_BACK" | "KEY_EXIT" => (Action::Press { press: vec![Key::Escape] }, Repeat::Flag(false)),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LG_CONF: &str = "
+# LG TV remote
+begin remote
+  name  LG_TV
+  bits           16
+  flags SPACE_ENC|CONST_LENGTH
+  header       9000  4500
+  pre_data_bits   16
+  pre_data       0x20DF
+
+  begin codes
+    KEY_POWER                0x10EF  # power
+    KEY_VOLUMEUP             0x40BF
+  end codes
+end remote
+";
+
+    #[test]
+    fn parses_remote() {
+        let remotes = LircRemote::parse(LG_CONF).unwrap();
+        assert_eq!(remotes.len(), 1);
+
+        let remote = &remotes[0];
+        assert_eq!(remote.name, "LG_TV");
+        assert_eq!(remote.bits, 16);
+        assert_eq!(remote.pre_data_bits, 16);
+        assert_eq!(remote.pre_data, 0x20DF);
+        assert_eq!(remote.header, Some((9000, 4500)));
+        assert_eq!(remote.codes, vec![(str!("KEY_POWER"), 0x10EF), (str!("KEY_VOLUMEUP"), 0x40BF)]);
+    }
+
+    #[test]
+    fn builds_code_hex_and_address() {
+        let remote = &LircRemote::parse(LG_CONF).unwrap()[0];
+
+        assert!(remote.is_nec());
+        assert_eq!(remote.code_hex(0x10EF), "20DF10EF");
+        assert_eq!(remote.address(), Some(str!("20DF")));
+    }
+
+    #[test]
+    fn mixed_addresses_have_no_address() {
+        let mut remote = LircRemote::parse(LG_CONF).unwrap().remove(0);
+        remote.pre_data_bits = 0;
+        remote.pre_data = 0;
+        remote.bits = 32;
+        remote.codes = vec![(str!("KEY_POWER"), 0x20DF10EF), (str!("KEY_MUTE"), 0x00FF38C7)];
+
+        assert_eq!(remote.address(), None);
+    }
+
+    #[test]
+    fn keeps_64_bits_codes() {
+        let remote = LircRemote { bits: 64, ..LircRemote::default() };
+
+        assert_eq!(remote.code_value(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn rejects_too_long_codes() {
+        let conf = LG_CONF.replace("bits           16", "bits           64");
+        assert!(LircRemote::parse(&conf).is_err());
+
+        let conf = LG_CONF.replace("bits           16", "bits           65");
+        assert!(LircRemote::parse(&conf).is_err());
+    }
+
+    #[test]
+    fn matches_non_nec_codes_by_prefix() {
+        let conf = LG_CONF.replace("header       9000  4500\n", "").replace("0x20DF", "0x0A5F");
+        let remote = LircRemote::parse(&conf).unwrap()[0].to_remote(false).unwrap();
+
+        assert_eq!(remote.address, None);
+        assert_eq!(remote.prefix, Some(str!("A5F")));
+
+        for code in remote.binds.keys() {
+            assert!(remote.matches(&crate::Code::parse(code).unwrap()), "'{code}' isn't matched");
+        }
+    }
+
+    #[test]
+    fn matches_nec_codes_by_address() {
+        let remote = LircRemote::parse(LG_CONF).unwrap()[0].to_remote(false).unwrap();
+
+        assert_eq!(remote.address, Some(str!("20DF")));
+
+        for code in remote.binds.keys() {
+            assert!(remote.matches(&crate::Code::parse(code).unwrap()), "'{code}' isn't matched");
+        }
+    }
+
+    #[test]
+    fn rejects_unrecognizable_remote() {
+        let conf = "begin remote\n  name RC5\n  bits 13\n  begin codes\n    KEY_POWER 0x100C\n    KEY_MUTE 0x100D\n  end codes\nend remote\n";
+
+        assert!(LircRemote::parse(conf).unwrap()[0].to_remote(false).is_err());
+    }
+
+    #[test]
+    fn rejects_raw_codes() {
+        let conf = "begin remote\n  name TV\n  begin raw_codes\n  end raw_codes\nend remote\n";
+        assert!(LircRemote::parse(conf).is_err());
+    }
+}
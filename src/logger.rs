@@ -1,8 +1,12 @@
 use crate::prelude::*;
+use tokio::sync::broadcast::{ self, Sender, Receiver };
+use std::sync::atomic::{ AtomicBool, Ordering };
 
 /// The program logger
 pub struct Logger {
     pub logs: StdMutex<Vec<String>>,
+    sender: Sender<String>,
+    quiet: AtomicBool,
 }
 
 impl log::Log for Logger {
@@ -15,7 +19,12 @@ impl log::Log for Logger {
             let log = fmt!("[{}] {}", record.level(), record.args());
 
             // printing to terminal:
-            println!("{log}");
+            if !self.quiet.load(Ordering::Relaxed) {
+                println!("{log}");
+            }
+
+            // sending to subscribers:
+            let _ = self.sender.send(log.clone());
 
             self.logs.lock().unwrap().push(log);
         }
@@ -28,9 +37,28 @@ impl Logger {
     /// Creates a new logger
     pub fn new() -> Self {
         Self {
-            logs: StdMutex::new(vec![])
+            logs: StdMutex::new(vec![]),
+            sender: broadcast::channel(256).0,
+            quiet: AtomicBool::new(false),
         }
     }
+
+    /// Stops/resumes printing to terminal (logs are still collected and sent to subscribers)
+    pub fn set_quiet(&self, quiet: bool) {
+        self.quiet.store(quiet, Ordering::Relaxed);
+    }
+
+    /// Subscribes to new log lines
+    pub fn subscribe(&self) -> Receiver<String> {
+        self.sender.subscribe()
+    }
+
+    /// Returns the last logs (without clearing)
+    pub fn recent(&self, count: usize) -> Vec<String> {
+        let logs = self.logs.lock().unwrap();
+
+        logs[logs.len().saturating_sub(count)..].to_vec()
+    }
     
     /// Collects logs and clears them
     pub fn take(&self) -> Vec<String> {
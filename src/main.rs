@@ -1,30 +1,230 @@
-use app::{ prelude::*, Bind, Action, Keyboard, Key, Mouse, Media, Device };
-use core::time::Duration;
-use std::{ io::{ BufReader, BufRead }, process::Command };
+use app::{ prelude::*, InputEvent, SerialReader, Recorder, Notifier, Replayer, ReplaySpeed, Simulator, Keyboard, Mouse, Media, MediaEvent, MediaPoller, PollerHandle, Device, DeviceFilter, Power, Mpris, Dispatcher, Dashboard, WebUi, handlers::{ Devices, Registry } };
+use app::{ Cli, Command, RunArgs, config_schema, CtlCommand, ControlServer, ControlClient, ControlResponse, PidFile, LircRemote, Bundle, control, service };
+use tokio::sync::broadcast::error::RecvError;
+use clap::Parser;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    run().await?;
+    let cli = Cli::parse();
+
+    match cli.command {
+        None => run(cli.run, false).await?,
+        Some(Command::Run(args)) => run(args, false).await?,
+        Some(Command::Tui(args)) => run(args, true).await?,
+        Some(Command::Replay { file, speed, fast, dry_run }) => {
+            let speed = if fast { ReplaySpeed::Fast }
+                else if speed == 1.0 { ReplaySpeed::Original }
+                else { ReplaySpeed::Scaled(speed) };
+
+            replay(file, speed, dry_run).await?
+        }
+        Some(Command::Simulate { script }) => simulate(script).await?,
+        Some(Command::Schema { output }) => schema(output)?,
+        Some(Command::ImportLirc { file, name, suggest, merge }) => import_lirc(file, name, suggest, merge).await?,
+        Some(Command::Export { file }) => export(file).await?,
+        Some(Command::Import { file, overwrite }) => import(file, overwrite).await?,
+        Some(Command::Ctl { command }) => ctl(command).await?,
+        Some(Command::SystemdUnit { install }) => systemd_unit(install)?,
+    }
+
+    Ok(())
+}
+
+/// Runs the virtual Arduino
+async fn simulate(script: Option<PathBuf>) -> Result<()> {
+    let cfg = CONFIG.lock().await.clone();
+    let mut simulator = Simulator::open(cfg)?;
+
+    println!("Virtual Arduino port: {}", simulator.path().display());
+    println!("Set it as input port ('ports' in config), then send bind names, hex codes, 'repeat [count] [ms]', 'hold <bind> <ms>', 'wait <ms>', 'binds' or 'quit'.");
+
+    match script {
+        Some(path) => simulator.run_script(path),
+        None => simulator.run(std::io::stdin().lock(), true),
+    }
+}
+
+/// Prints or writes the config JSON Schema
+fn schema(output: Option<PathBuf>) -> Result<()> {
+    let schema = serde_json::to_string_pretty(&config_schema(&Registry::with_builtins()))?;
+
+    match output {
+        Some(path) => std::fs::write(path, schema)?,
+        None => println!("{schema}"),
+    }
+
+    Ok(())
+}
+
+/// Converts LIRC remotes to the remote config (prints or adds them to config)
+async fn import_lirc(file: PathBuf, name: Option<String>, suggest: bool, merge: bool) -> Result<()> {
+    let lirc_remotes = LircRemote::read(&file)?;
+    let count = lirc_remotes.len();
+
+    let mut remotes = HashMap::new();
+    for (index, lirc) in lirc_remotes.iter().enumerate() {
+        // the custom name is numbered for multiple remotes:
+        let name = match &name {
+            Some(name) if count > 1 => fmt!("{name}-{}", index + 1),
+            Some(name) => name.clone(),
+            None => lirc.name.clone(),
+        };
+
+        remotes.insert(name, lirc.to_remote(suggest)?);
+    }
+
+    if !merge {
+        println!("{}", serde_json::to_string_pretty(&remotes)?);
+        return Ok(());
+    }
+
+    let mut current = CONFIG.lock().await;
+    let mut cfg = current.clone();
+    if let Some(name) = remotes.keys().find(|name| cfg.remotes.contains_key(*name)) {
+        return Err(Error::RemoteExists(name.clone()).into());
+    }
+
+    let added = remotes.iter().map(|(name, remote)| fmt!("Added remote '{name}' ({} binds)", remote.binds.len())).collect::<Vec<_>>();
+    cfg.remotes.extend(remotes);
+
+    // check the merged config before saving:
+    Dispatcher::check_config(&Registry::with_builtins(), &cfg)?;
+    cfg.save()?;
+    *current = cfg;
+
+    for line in added {
+        println!("{line}");
+    }
+
     Ok(())
 }
 
-async fn run() -> Result<()> {
+/// Writes the keymap bundle
+async fn export(file: PathBuf) -> Result<()> {
+    let cfg = CONFIG.lock().await;
+    let bundle = Bundle::export(&cfg, root_path("/scripts")?)?;
+
+    bundle.write(&file)?;
+    println!("Exported {} binds, {} remotes and {} scripts to '{}'", bundle.binds.len(), bundle.remotes.len(), bundle.scripts.len(), file.display());
+
+    Ok(())
+}
+
+/// Merges the keymap bundle into config
+async fn import(file: PathBuf, overwrite: bool) -> Result<()> {
+    let bundle = Bundle::read(&file)?;
+
+    let mut current = CONFIG.lock().await;
+    let mut cfg = current.clone();
+    let conflicts = bundle.merge(&mut cfg, root_path("/scripts")?, overwrite)?;
+
+    // check the merged config before saving:
+    Dispatcher::check_config(&Registry::with_builtins(), &cfg)?;
+    cfg.save()?;
+    *current = cfg;
+
+    for conflict in &conflicts {
+        println!("{conflict}");
+    }
+
+    match (conflicts.len(), overwrite) {
+        (0, _) => println!("Imported '{}'", file.display()),
+        (count, true) => println!("Imported '{}', replaced {count} conflicting entries", file.display()),
+        (count, false) => println!("Imported '{}', kept {count} conflicting entries (use --overwrite to replace them)", file.display()),
+    }
+
+    Ok(())
+}
+
+/// Prints or installs the systemd user unit
+fn systemd_unit(install: bool) -> Result<()> {
+    if install {
+        let path = service::install_systemd_unit()?;
+        println!("Installed '{}', enable it with: systemctl --user enable --now avr-pc-remote", path.display());
+    } else {
+        print!("{}", service::systemd_unit()?);
+    }
+
+    Ok(())
+}
+
+/// Sends a control request to the running instance and prints responses
+async fn ctl(command: CtlCommand) -> Result<()> {
+    let cfg = CONFIG.lock().await.clone();
+    let path = control::socket_path(&cfg);
+
+    let mut client = ControlClient::connect(&path).await
+        .map_err(|e| Error::ControlError(fmt!("couldn't connect to '{path}' ({e}), is the remote running?")))?;
+
+    client.send(&command.to_request()?).await?;
+
+    while let Some(response) = client.next().await? {
+        let is_final = response.is_final();
+
+        match response {
+            ControlResponse::Ok { message } => if !message.is_empty() { println!("{message}") },
+            ControlResponse::State { state } => println!("{}", serde_json::to_string_pretty(&state)?),
+            ControlResponse::Log { line } => println!("{line}"),
+            ControlResponse::Error { message } => return Err(Error::ControlError(message).into()),
+        }
+
+        if is_final { break }
+    }
+
+    Ok(())
+}
+
+/// Inits logger and config
+async fn init() -> Result<Config> {
     // init logger:
     log::set_logger(&*LOGGER).map_err(Error::from)?;
     log::set_max_level(log::LevelFilter::Info);
     
     // init config:
     CONFIG.lock().await.init();
-    let cfg = CONFIG.lock().await.clone();
+
+    Ok(CONFIG.lock().await.clone())
+}
+
+/// Inits the controlled devices (or the dry-run backend)
+async fn init_devices(cfg: &Config, dry_run: bool) -> Result<Devices> {
+    if dry_run {
+        info!("Using dry-run backend..");
+
+        return Ok(Devices {
+            media: Media::dry_run(DeviceFilter::new(&cfg.audio)?, DeviceFilter::new(&cfg.micro)?),
+            keyboard: Keyboard::dry_run().with_key_delay(Duration::from_millis(cfg.key_delay_ms)),
+            mouse: Mouse::dry_run(),
+            power: Power::dry_run(root_path("/bin")?, cfg.power_commands.clone()),
+            mpris: None,
+            dry_run: true,
+        });
+    }
 
     // init media controller:
-    let mut media = Media::new(root_path("/bin")?, Some(|name| !name.contains("SteelSeries"))).await?;
+    let media = Media::new(root_path("/bin")?, DeviceFilter::new(&cfg.audio)?, DeviceFilter::new(&cfg.micro)?).await?;
 
     // init keyboard:
-    let mut keyboard = Keyboard::new()?;
+    let keyboard = Keyboard::new()?.with_key_delay(Duration::from_millis(cfg.key_delay_ms));
 
     // init mouse:
-    let mut mouse = Mouse::new()?;
+    let mouse = Mouse::new()?;
+
+    // init power controller:
+    let power = Power::new(root_path("/bin")?, cfg.power_commands.clone());
+
+    // init MPRIS media controller:
+    let mpris = if cfg.mpris.enabled {
+        match Mpris::connect(cfg.mpris.bus_address.as_deref(), cfg.mpris.player.clone()).await {
+            Ok(mpris) => Some(mpris),
+            Err(e) => {
+                info!("MPRIS isn't available ({e}), using media keys..");
+                None
+            }
+        }
+    } else {
+        None
+    };
 
     // print audio device list:
     info!("Audio device list: \n{}",
@@ -37,284 +237,155 @@ async fn run() -> Result<()> {
             .join("\n")
     );
 
-    let port_name = fmt!("COM{}", cfg.com_port);
-    let baud_rate = cfg.baud_rate as u32;
+    // print application sessions list:
+    match media.get_sessions().await {
+        Ok(sessions) => info!("Application sessions: \n{}",
+            sessions.iter()
+                .map(|session| fmt!("'{}' (PID {}) — {}%{}",
+                    session.name,
+                    session.pid.map(|pid| pid.to_string()).unwrap_or(str!("?")),
+                    session.volume,
+                    if session.is_muted {" (muted)"}else{""}
+                ))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+        Err(e) => err!("Failed to read application sessions: {e}"),
+    }
 
-    let port = serialport::new(&port_name, baud_rate)
-        .timeout(Duration::from_millis(10))
-        .open()?;
+    Ok(Devices { media, keyboard, mouse, power, mpris, dry_run: false })
+}
 
-    let mut com_reader = BufReader::new(port);
-    let mut line = String::new();
+/// Starts the media info poller (logs the media state changes, both are stopped by the dispatcher shutdown)
+fn spawn_media_poller(cfg: &Config, media: Media) -> PollerHandle {
+    let poller = MediaPoller::new(media, Duration::from_millis(cfg.media_poll_ms));
+    let mut media_events = poller.subscribe();
+    let poller = poller.spawn();
+
+    let listener = tokio::spawn(async move {
+        loop {
+            match media_events.recv().await {
+                Ok(MediaEvent::ActiveChanged(Some(device))) => info!("Audio device changed to '{}'", device.name),
+                Ok(MediaEvent::VolumeChanged(volume)) => info!("Audio volume changed to {volume}%"),
+                Ok(MediaEvent::AudioMuteChanged(muted)) => info!("Audio {}", if muted { "muted" }else{ "unmuted" }),
+                Ok(MediaEvent::MicroMuteChanged(muted)) => info!("Microphone {}", if muted { "muted" }else{ "unmuted" }),
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
 
-    let binds = cfg.binds;
-    let mut last_code = str!();
-    let mut last_action = Instant::now();
-    let mut last_update = Instant::now();
-    let action_interval = Duration::from_millis(1000);
-    let update_interval = Duration::from_millis(2000);
-    let repeat_timeout = Duration::from_millis(25);
+    poller.with_listener(listener)
+}
 
-    info!("Reading remote inputs..");
-    
-    loop {
-        line.clear();
+/// Reads remote inputs until a termination signal (or the dashboard exit)
+async fn run(args: RunArgs, dashboard: bool) -> Result<()> {
+    // keep the terminal clean for the dashboard:
+    if dashboard {
+        LOGGER.set_quiet(true);
+    }
 
-        if last_action.elapsed() >= action_interval {
-            if last_update.elapsed() >= update_interval {
-                media.update_info().await?;
-                last_update = Instant::now();
-            }
-        } else {
-            last_update = Instant::now();
-        }
+    let cfg = init().await?;
 
-        match com_reader.read_line(&mut line) {
-            Ok(0) => continue,
-            Ok(_) => {
-                let code = line.trim().to_uppercase();
-                if code.is_empty() { continue }
-
-                // repeat last bind:
-                if code == "FFFFFFFF" {
-                    if last_action.elapsed() < repeat_timeout {
-                        continue;
-                    }
-                    
-                    if last_code.is_empty() { continue }
-
-                    if let Err(e) = execute_bind(binds.get(&last_code).unwrap(), &mut media, &mut keyboard, &mut mouse, true).await {
-                        err!("Error with executing bind: {e}");
-                    }
-                } else {
-                    // execute exists bind:
-                    if let Some(bind) = binds.get(&code) {
-                        info!("Pressed '{code}', bind '{}'.", bind.name);
-
-                        last_code = if bind.repeat { code }else{ str!() };
-                        
-                        if let Err(e) = execute_bind(bind, &mut media, &mut keyboard, &mut mouse, false).await {
-                            err!("Error with executing bind: {e}");
-                        }
-                    }
-                    // unbinded code:
-                    else {
-                        info!("Pressed '{code}', no binds exists..");
-                        last_code = str!();
-                    }
-                }
-
-                last_action = Instant::now();
-            }
-            Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => continue,
-            Err(e) => {
-                err!("Error with reading '{port_name}' port: {e}");
-                return Err(e.into());
-            }
+    // lock the single instance:
+    let _pid_file = PidFile::acquire(service::pid_path(&cfg))?;
+
+    // init dispatcher:
+    let devices = init_devices(&cfg, args.dry_run).await?;
+    let mut dispatcher = Dispatcher::new(cfg.clone(), Registry::with_builtins(), devices)?;
+
+    if !args.dry_run {
+        dispatcher.poll_media(spawn_media_poller(&cfg, dispatcher.devices.media.clone()));
+    }
+
+    if let Some(path) = &args.record {
+        dispatcher.record_to(Recorder::create(path)?);
+    }
+
+    if cfg.notifications.enabled {
+        dispatcher.notify_to(Notifier::spawn(cfg.notifications.clone(), args.dry_run));
+    }
+
+    // start reading input ports:
+    let (sender, receiver) = tokio::sync::mpsc::channel::<InputEvent>(64);
+
+    let readers = cfg.get_ports().into_iter()
+        .map(|port| SerialReader::new(port).spawn(sender.clone()))
+        .collect::<Vec<_>>();
+    drop(sender);
+
+    // start control socket:
+    let socket_path = control::socket_path(&cfg);
+    let (control_sender, control_receiver) = tokio::sync::mpsc::channel(16);
+    ControlServer::new(&socket_path, control_sender.clone()).spawn();
+
+    // start web UI:
+    if let Some(addr) = &cfg.web_ui {
+        WebUi::new(addr, dispatcher.registry().clone(), dispatcher.event_sender(), control_sender.clone())?.spawn();
+    }
+
+    // start terminal dashboard:
+    let dashboard = dashboard.then(|| Dashboard::new(&cfg, dispatcher.devices.media.clone(), dispatcher.subscribe(), control_sender));
+    let dashboard = async move {
+        match dashboard {
+            Some(dashboard) => dashboard.run().await,
+            None => std::future::pending().await,
         }
+    };
+
+    // run until all ports are closed, a termination signal or the dashboard exit:
+    let result = tokio::select! {
+        result = dispatcher.run(receiver, control_receiver) => result,
+        result = service::shutdown_signal() => result,
+        result = dashboard => result,
+    };
+
+    // graceful shutdown (stops the media poller, the readers close ports once the receiver is dropped):
+    dispatcher.shutdown().await;
+
+    for reader in readers {
+        let _ = tokio::time::timeout(Duration::from_secs(1), reader).await;
     }
-}
 
-static MENU_MODE: Lazy<Mutex<u8>> = Lazy::new(|| Mutex::new(0));
-const MOUSE_STEPS: (i32, i32) = (30, 100);
-const SCROLL_STEPS: (i32, i32) = (2, 5);
-const VOLUME_STEPS: (i32, i32) = (2, 5);
-
-/// Execute remote bind
-async fn execute_bind(bind: &Bind, media: &mut Media, keyboard: &mut Keyboard, mouse: &mut Mouse, is_repeated: bool) -> Result<()> {
-    match &bind.action {
-        // execute special handler:
-        Action::Handler { handler: name } => {
-            match name.as_ref() {
-                "switch-audio" => {
-                    media.switch_next_audio_device().await?;
-                }
-
-                "mute-unmute" => {
-                    media.switch_audio_mute().await?;
-                    media.switch_micro_mute().await?;
-                }
-
-                "navigation" => {
-                    let mut mode = MENU_MODE.lock().await;
-
-                    if *mode >= 2 {
-                        *mode = 0;
-                    } else {
-                        *mode += 1;
-                    }
-
-                    match *mode {
-                        0 => info!("Switched to media mode"),
-                        1 => info!("Switched to mouse emulation mode"),
-                        2 => info!("Switched to selector mode"),
-
-                        _ => err!("Unknown menu mode")
-                    }
-                }
-                "nav-left" => {
-                    match *MENU_MODE.lock().await {
-                        0 => {
-                            let volume = media.decrease_audio_volume(if is_repeated { VOLUME_STEPS.1 }else{ VOLUME_STEPS.0 }).await?;
-                            info!("Set audio volume to {volume}%");
-                        }
-
-                        1 => {
-                            let step: i32 = if is_repeated { MOUSE_STEPS.1 }else{ MOUSE_STEPS.0 };
-                            mouse.move_x(-step)?;
-                            info!("Move mouse left by {step}px", );
-                        }
-
-                        2 => {
-                            keyboard.press_all(&[Key::Shift, Key::Tab], true).await?;
-                            sleep(Duration::from_millis(100)).await;
-                            keyboard.release_all(&[Key::Shift, Key::Tab]).await?;
-                            info!("Selected previous element");
-                        }
-
-                        _ => err!("Unknown menu mode")
-                    }
-                }
-                "nav-right" => {
-                    match *MENU_MODE.lock().await {
-                        0 => {
-                            let volume = media.increase_audio_volume(if is_repeated { VOLUME_STEPS.1 }else{ VOLUME_STEPS.0 }).await?;
-                            info!("Set audio volume to {volume}%");
-                        }
-
-                        1 => {
-                            let step: i32 = if is_repeated { MOUSE_STEPS.1 }else{ MOUSE_STEPS.0 };
-                            mouse.move_x(step)?;
-                            info!("Move mouse right by {step}px", );
-                        }
-
-                        2 => {
-                            keyboard.press(&Key::Tab, true).await?;
-                            info!("Selected next element");
-                        }
-
-                        _ => err!("Unknown menu mode")
-                    }
-                }
-                "nav-up" => {
-                    match *MENU_MODE.lock().await {
-                        0 => {
-                            if !is_repeated {
-                                keyboard.press(&Key::MediaNextTrack, false).await?;
-                                info!("Switched to next track");
-                            }
-                        }
-
-                        1 => {
-                            let step: i32 = if is_repeated { MOUSE_STEPS.1 }else{ MOUSE_STEPS.0 };
-                            mouse.move_y(-step)?;
-                            info!("Move mouse top by {step}px", );
-                        }
-
-                        2 => {
-                            keyboard.press_all(&[Key::Shift, Key::Tab], true).await?;
-                            sleep(Duration::from_millis(100)).await;
-                            keyboard.release_all(&[Key::Shift, Key::Tab]).await?;
-                            info!("Selected previous element");
-                        }
-
-                        _ => err!("Unknown menu mode")
-                    }
-                }
-                "nav-down" => {
-                    match *MENU_MODE.lock().await {
-                        0 => {
-                            if !is_repeated {
-                                keyboard.press(&Key::MediaPrevTrack, false).await?;
-                                info!("Switched to previos track");
-                            }
-                        }
-
-                        1 => {
-                            let step: i32 = if is_repeated { MOUSE_STEPS.1 }else{ MOUSE_STEPS.0 };
-                            mouse.move_y(step)?;
-                            info!("Move mouse bottom by {step}px", );
-                        }
-                        
-                        2 => {
-                            keyboard.press(&Key::Tab, true).await?;
-                            info!("Selected next element");
-                        }
-
-                        _ => err!("Unknown menu mode")
-                    }
-                }
-                "nav-center" => {
-                    match *MENU_MODE.lock().await {
-                        0 => {
-                            keyboard.press(&Key::MediaPlayPause, false).await?;
-                            info!("Switched media play/pause");
-                        }
-
-                        1 => {
-                            mouse.press_left(false)?;
-                            info!("Pressed left mouse button");
-                        }
-
-                        2 => {
-                            keyboard.press(&Key::Enter, false).await?;
-                            info!("Pressed enter button");
-                        }
-
-                        _ => err!("Unknown menu mode")
-                    }
-                }
-
-                "scroll-up" => {
-                    let step: i32 = if is_repeated { SCROLL_STEPS.1 }else{ SCROLL_STEPS.0 };
-                    mouse.scroll_y(-step)?;
-                    info!("Scroll up by {step}px");
-                }
-                "scroll-down" => {
-                    let step: i32 = if is_repeated { SCROLL_STEPS.1 }else{ SCROLL_STEPS.0 };
-                    mouse.scroll_y(step)?;
-                    info!("Scroll down by {step}px");
-                }
-
-                "sleep-mode" => {
-                    let status = Command::new("rundll32.exe")
-                        .arg("powrprof.dll,SetSuspendState")
-                        .arg("0")
-                        .arg("1")
-                        .arg("0")
-                        .status()?;
-
-                    if status.success() {
-                        info!("PC switched to sleep mode");
-                    } else {
-                        err!("Failed switch PC to sleep mode");
-                    }
-                }
-                
-                _ => err!("Unknown handler with name '{name}'")
-            }
-        },
+    if cfg!(unix) {
+        let _ = std::fs::remove_file(&socket_path);
+    }
 
-        // press keyboard shortcut:
-        Action::Shortcut { shortcut: keys } => {
-            keyboard.press_all(keys, true).await?;
+    info!("Stopped");
+    result
+}
 
-            sleep(Duration::from_millis(100)).await;
+/// Feeds a record file through the dispatcher
+async fn replay(file: PathBuf, speed: ReplaySpeed, dry_run: bool) -> Result<()> {
+    let cfg = init().await?;
 
-            keyboard.release_all(keys).await?;
-        },
+    // init dispatcher:
+    let devices = init_devices(&cfg, dry_run).await?;
+    let mut dispatcher = Dispatcher::new(cfg.clone(), Registry::with_builtins(), devices)?;
 
-        // press keyboard key:
-        Action::Press { press: keys } => {
-            keyboard.press_all(keys, false).await?;
-        },
+    if !dry_run {
+        dispatcher.poll_media(spawn_media_poller(&cfg, dispatcher.devices.media.clone()));
+    }
 
-        // open website:
-        Action::Open { open: url } => {
-            let url = if url.starts_with("https:") { url }else{ &fmt!("https://{url}") };
-            webbrowser::open(url)?;
-        },
+    // start replaying:
+    let replayer = Replayer::open(&file, speed)?;
+    info!("Replaying {} input events from '{}'..", replayer.len(), file.display());
+
+    if speed != ReplaySpeed::Original {
+        info!("Repeats are gated by the wall clock, so the changed speed may drop or add bind repeats");
     }
 
-    Ok(())
+    let (sender, receiver) = tokio::sync::mpsc::channel::<InputEvent>(64);
+    replayer.spawn(sender);
+
+    let (_control_sender, control_receiver) = tokio::sync::mpsc::channel(1);
+    let result = dispatcher.run(receiver, control_receiver).await;
+
+    dispatcher.shutdown().await;
+
+    // the record is over:
+    match result {
+        Err(e) if matches!(e.downcast_ref::<Error>(), Some(Error::NoInputPorts)) => Ok(()),
+        result => result,
+    }
 }
@@ -0,0 +1,177 @@
+use crate::{ prelude::*, NotifyConfig, Snapshot };
+use tokio::sync::mpsc::{ self, UnboundedSender };
+
+/// The notification kind (notifications of the same kind are coalesced)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum NotifyKind {
+    Mode,
+    Volume,
+    AudioDevice,
+    MicroDevice,
+    Error,
+}
+
+/// The desktop notification
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub kind: NotifyKind,
+    pub summary: String,
+    pub body: String,
+}
+
+impl Notification {
+    /// Creates a new notification
+    pub fn new<S: Into<String>, B: Into<String>>(kind: NotifyKind, summary: S, body: B) -> Self {
+        Self { kind, summary: summary.into(), body: body.into() }
+    }
+}
+
+/// The desktop notifier (freedesktop notifications on Linux, toasts on Windows)
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    config: NotifyConfig,
+    sender: UnboundedSender<Notification>,
+}
+
+impl Notifier {
+    const APP_NAME: &str = "AVR PC Remote";
+
+    /// Starts the notifications task (shows one notification per kind in the coalescing window, the latest wins)
+    /// (a new notification replaces the shown one of the same kind)
+    pub fn spawn(config: NotifyConfig, dry_run: bool) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<Notification>();
+        let window = Duration::from_millis(config.coalesce_ms);
+        let timeout = config.timeout_ms;
+
+        tokio::spawn(async move {
+            let mut last_shown: HashMap<NotifyKind, Instant> = HashMap::new();
+            let mut pending: HashMap<NotifyKind, Notification> = HashMap::new();
+            let mut shown_ids: HashMap<NotifyKind, u32> = HashMap::new();
+
+            loop {
+                // wait for a new notification or the nearest pending one:
+                let deadline = pending.keys()
+                    .filter_map(|kind| last_shown.get(kind))
+                    .map(|shown| *shown + window)
+                    .min();
+
+                let received = match deadline {
+                    Some(deadline) => tokio::select! {
+                        notification = receiver.recv() => Some(notification),
+                        _ = tokio::time::sleep_until(deadline.into()) => None,
+                    },
+                    None => Some(receiver.recv().await),
+                };
+
+                match received {
+                    Some(Some(notification)) => { pending.insert(notification.kind, notification); }
+                    Some(None) => break,
+                    None => {}
+                }
+
+                // show notifications out of their coalescing window:
+                let ready = pending.keys()
+                    .filter(|kind| last_shown.get(kind).map(|shown| shown.elapsed() >= window).unwrap_or(true))
+                    .copied()
+                    .collect::<Vec<_>>();
+
+                for kind in ready {
+                    let Some(notification) = pending.remove(&kind) else { continue };
+                    last_shown.insert(kind, Instant::now());
+
+                    match Self::show(notification, shown_ids.get(&kind).copied(), timeout, dry_run).await {
+                        Some(id) => { shown_ids.insert(kind, id); }
+                        None => { shown_ids.remove(&kind); }
+                    }
+                }
+            }
+        });
+
+        Self { config, sender }
+    }
+
+    /// Queues a notification (if its kind is enabled)
+    pub fn notify(&self, notification: Notification) {
+        let enabled = match notification.kind {
+            NotifyKind::Mode => self.config.mode,
+            NotifyKind::Volume => self.config.volume,
+            NotifyKind::AudioDevice | NotifyKind::MicroDevice => self.config.devices,
+            NotifyKind::Error => self.config.errors,
+        };
+
+        if enabled {
+            let _ = self.sender.send(notification);
+        }
+    }
+
+    /// Queues notifications about the state changes
+    pub fn notify_changes(&self, before: &Snapshot, after: &Snapshot) {
+        if before.mode != after.mode {
+            self.notify(Notification::new(NotifyKind::Mode, "Navigation mode", after.mode.to_string()));
+        }
+
+        if before.audio_device != after.audio_device {
+            if let Some(name) = &after.audio_device {
+                self.notify(Notification::new(NotifyKind::AudioDevice, "Audio device", name.clone()));
+            }
+        }
+        if before.micro_device != after.micro_device {
+            if let Some(name) = &after.micro_device {
+                self.notify(Notification::new(NotifyKind::MicroDevice, "Microphone", name.clone()));
+            }
+        }
+
+        // volume and mute changes are shown together:
+        let mut lines = vec![];
+        if before.volume != after.volume || before.audio_muted != after.audio_muted {
+            lines.push(if after.audio_muted { str!("Muted") } else { fmt!("{}%", after.volume) });
+        }
+        if before.micro_muted != after.micro_muted {
+            lines.push(fmt!("Microphone {}", if after.micro_muted { "muted" } else { "unmuted" }));
+        }
+
+        if !lines.is_empty() {
+            self.notify(Notification::new(NotifyKind::Volume, "Volume", lines.join("\n")));
+        }
+    }
+
+    /// Shows a notification replacing the previous one by its id (logs it in dry-run), returns the shown notification id
+    async fn show(notification: Notification, replace_id: Option<u32>, timeout_ms: u64, dry_run: bool) -> Option<u32> {
+        if dry_run {
+            info!("[dry-run] notify '{}': {}", notification.summary, notification.body);
+            return None;
+        }
+
+        let result = tokio::task::spawn_blocking(move || {
+            let mut toast = notify_rust::Notification::new();
+            toast.appname(Self::APP_NAME)
+                .summary(&notification.summary)
+                .body(&notification.body)
+                .timeout(notify_rust::Timeout::Milliseconds(timeout_ms as u32));
+
+            if let Some(id) = replace_id {
+                toast.id(id);
+            }
+
+            toast.show().map(handle_id)
+        }).await;
+
+        match result {
+            Ok(Err(e)) => { err!("Error with showing notification: {e}"); None }
+            Err(e) => { err!("Error with showing notification: {e}"); None }
+            Ok(Ok(id)) => id,
+        }
+    }
+}
+
+/// Returns the shown notification id (to replace it by the next one)
+#[cfg(target_os = "linux")]
+fn handle_id(handle: notify_rust::NotificationHandle) -> Option<u32> {
+    Some(handle.id())
+}
+
+/// Returns the shown notification id (toasts on Windows can't be replaced, so they're stacked)
+#[cfg(not(target_os = "linux"))]
+fn handle_id<H>(_handle: H) -> Option<u32> {
+    None
+}
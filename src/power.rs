@@ -0,0 +1,127 @@
+use crate::prelude::*;
+use std::process::Command;
+
+/// The power operation
+#[derive(Debug, Display, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum PowerAction {
+    Suspend,
+    Hibernate,
+    Shutdown,
+    Reboot,
+    Lock,
+    ScreenOff,
+}
+
+/// The power controller
+#[derive(Debug, Clone)]
+pub struct Power {
+    nircmd_path: PathBuf,
+    overrides: HashMap<PowerAction, Vec<String>>,
+    dry_run: bool,
+}
+
+impl Power {
+    /// Creates a new power controller
+    pub fn new<P: AsRef<Path>>(bin_path: P, overrides: HashMap<PowerAction, Vec<String>>) -> Self {
+        Self {
+            nircmd_path: bin_path.as_ref().join("nircmd/nircmd.exe"),
+            overrides,
+            dry_run: false,
+        }
+    }
+
+    /// Creates a dry-run power controller (logs commands instead of executing)
+    pub fn dry_run<P: AsRef<Path>>(bin_path: P, overrides: HashMap<PowerAction, Vec<String>>) -> Self {
+        Self {
+            dry_run: true,
+            ..Self::new(bin_path, overrides)
+        }
+    }
+
+    /// Suspend PC (sleep mode)
+    pub fn suspend(&self) -> Result<()> {
+        self.execute(PowerAction::Suspend)
+    }
+
+    /// Hibernate PC
+    pub fn hibernate(&self) -> Result<()> {
+        self.execute(PowerAction::Hibernate)
+    }
+
+    /// Shutdown PC
+    pub fn shutdown(&self) -> Result<()> {
+        self.execute(PowerAction::Shutdown)
+    }
+
+    /// Reboot PC
+    pub fn reboot(&self) -> Result<()> {
+        self.execute(PowerAction::Reboot)
+    }
+
+    /// Lock user session
+    pub fn lock(&self) -> Result<()> {
+        self.execute(PowerAction::Lock)
+    }
+
+    /// Turn off the screen
+    pub fn screen_off(&self) -> Result<()> {
+        self.execute(PowerAction::ScreenOff)
+    }
+
+    /// Executes a power operation
+    pub fn execute(&self, action: PowerAction) -> Result<()> {
+        let command = self.command(action);
+        let (program, args) = command.split_first().ok_or_else(|| Error::UnsupportedPowerAction(action.to_string()))?;
+
+        if self.dry_run {
+            info!("[dry-run] power command: {}", command.join(" "));
+            return Ok(());
+        }
+
+        let status = Command::new(program)
+            .args(args)
+            .status()?;
+
+        if !status.success() {
+            return Err(Error::FailedPowerAction(action.to_string()).into());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the power operation command (configured or platform default)
+    pub fn command(&self, action: PowerAction) -> Vec<String> {
+        if let Some(command) = self.overrides.get(&action) {
+            return command.clone();
+        }
+
+        let command: &[&str] = if cfg!(target_os = "windows") {
+            match action {
+                PowerAction::Suspend => &["rundll32.exe", "powrprof.dll,SetSuspendState", "0", "1", "0"],
+                PowerAction::Hibernate => &["shutdown", "/h"],
+                PowerAction::Shutdown => &["shutdown", "/s", "/t", "0"],
+                PowerAction::Reboot => &["shutdown", "/r", "/t", "0"],
+                PowerAction::Lock => &["rundll32.exe", "user32.dll,LockWorkStation"],
+                PowerAction::ScreenOff => {
+                    return vec![self.nircmd_path.to_string_lossy().to_string(), str!("monitor"), str!("off")];
+                }
+            }
+        }
+        else if cfg!(target_os = "linux") {
+            match action {
+                PowerAction::Suspend => &["systemctl", "suspend"],
+                PowerAction::Hibernate => &["systemctl", "hibernate"],
+                PowerAction::Shutdown => &["systemctl", "poweroff"],
+                PowerAction::Reboot => &["systemctl", "reboot"],
+                PowerAction::Lock => &["loginctl", "lock-session"],
+                PowerAction::ScreenOff => &["xset", "dpms", "force", "off"],
+            }
+        }
+        else {
+            &[]
+        };
+
+        command.iter().map(|arg| arg.to_string()).collect()
+    }
+}
@@ -10,6 +10,7 @@ pub use macron::*;
 pub use log::{ info, error as err};
 pub use once_cell::sync::Lazy;
 pub use serde::{ Serialize, Deserialize };
+pub use schemars::JsonSchema;
 pub use serde_json::Value;
 
 pub use std::format as fmt;
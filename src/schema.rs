@@ -0,0 +1,44 @@
+use crate::{ prelude::*, Registry, Key };
+use schemars::schema_for;
+
+/// Generates the config JSON Schema (with the registered handler names)
+pub fn config_schema(registry: &Registry) -> Value {
+    let mut schema = schema_for!(Config).to_value();
+
+    // complete handler names:
+    if let Some(variants) = schema.pointer_mut("/$defs/Action/anyOf").and_then(Value::as_array_mut) {
+        for variant in variants {
+            if let Some(handler) = variant.pointer_mut("/properties/handler") {
+                handler["enum"] = Value::from(registry.names());
+            }
+        }
+    }
+
+    schema
+}
+
+/// Returns the named keyboard keys (from the 'Key' schema)
+pub fn key_names() -> Vec<String> {
+    fn collect(schema: &Value, names: &mut Vec<String>) {
+        match schema {
+            Value::Object(map) => {
+                if let Some(Value::String(name)) = map.get("const") {
+                    names.push(name.clone());
+                }
+                if let Some(Value::Array(values)) = map.get("enum") {
+                    names.extend(values.iter().filter_map(Value::as_str).map(str::to_owned));
+                }
+
+                map.values().for_each(|value| collect(value, names));
+            }
+            Value::Array(values) => values.iter().for_each(|value| collect(value, names)),
+            _ => {}
+        }
+    }
+
+    let mut names = vec![];
+    collect(&schema_for!(Key).to_value(), &mut names);
+    names.dedup();
+
+    names
+}
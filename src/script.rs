@@ -0,0 +1,196 @@
+use crate::{ prelude::*, Action, Key, Mode };
+use rhai::{ Engine, Scope, Dynamic, Array, Map, EvalAltResult, AST };
+
+type ScriptResult<T> = StdResult<T, Box<EvalAltResult>>;
+
+/// The script command (executed after the script run)
+#[derive(Debug, Clone)]
+pub enum ScriptCommand {
+    Action(Action),
+    MouseMove(i32, i32),
+    MouseClick(bool),
+    SetMode(Mode),
+    Wait(u64),
+}
+
+/// The script environment values
+#[derive(Debug, Clone, Default)]
+pub struct ScriptEnv {
+    pub mode: Mode,
+    pub volume: i32,
+    pub audio_device: String,
+    pub repeats: u32,
+}
+
+/// The scripts engine (Rhai)
+pub struct ScriptEngine {
+    engine: Engine,
+    dir: PathBuf,
+    commands: Arc<StdMutex<Vec<ScriptCommand>>>,
+    deadline: Arc<StdMutex<Option<Instant>>>,
+    store: Arc<StdMutex<HashMap<String, Dynamic>>>,
+}
+
+impl ScriptEngine {
+    /// Creates a new scripts engine (scripts are loaded from the directory)
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        let commands = Arc::new(StdMutex::new(vec![]));
+        let deadline = Arc::new(StdMutex::new(None::<Instant>));
+        let store = Arc::new(StdMutex::new(HashMap::new()));
+
+        let mut engine = Engine::new();
+
+        // stop script after time limit:
+        let limit = deadline.clone();
+        engine.on_progress(move |_| {
+            match *limit.lock().unwrap() {
+                Some(deadline) if Instant::now() >= deadline => Some("time limit exceeded".into()),
+                _ => None
+            }
+        });
+
+        // print to log:
+        engine.on_print(|text| info!("[script] {text}"));
+
+        // keyboard:
+        let queue = commands.clone();
+        engine.register_fn("press", move |key: &str| -> ScriptResult<()> {
+            queue.lock().unwrap().push(ScriptCommand::Action(Action::Press { press: vec![parse_key(key)?] }));
+            Ok(())
+        });
+        let queue = commands.clone();
+        engine.register_fn("press", move |keys: Array| -> ScriptResult<()> {
+            queue.lock().unwrap().push(ScriptCommand::Action(Action::Press { press: parse_keys(keys)? }));
+            Ok(())
+        });
+        let queue = commands.clone();
+        engine.register_fn("shortcut", move |keys: Array| -> ScriptResult<()> {
+            queue.lock().unwrap().push(ScriptCommand::Action(Action::Shortcut { shortcut: parse_keys(keys)? }));
+            Ok(())
+        });
+        let queue = commands.clone();
+        engine.register_fn("key_down", move |keys: Array| -> ScriptResult<()> {
+            queue.lock().unwrap().push(ScriptCommand::Action(Action::KeyDown { key_down: parse_keys(keys)? }));
+            Ok(())
+        });
+        let queue = commands.clone();
+        engine.register_fn("key_up", move |keys: Array| -> ScriptResult<()> {
+            queue.lock().unwrap().push(ScriptCommand::Action(Action::KeyUp { key_up: parse_keys(keys)? }));
+            Ok(())
+        });
+        let queue = commands.clone();
+        engine.register_fn("hold", move |keys: Array, millis: i64| -> ScriptResult<()> {
+            queue.lock().unwrap().push(ScriptCommand::Action(Action::Hold { hold: parse_keys(keys)?, duration_ms: millis.max(0) as u64 }));
+            Ok(())
+        });
+
+        // mouse:
+        let queue = commands.clone();
+        engine.register_fn("mouse_move", move |dx: i64, dy: i64| {
+            queue.lock().unwrap().push(ScriptCommand::MouseMove(dx as i32, dy as i32));
+        });
+        let queue = commands.clone();
+        engine.register_fn("click", move || {
+            queue.lock().unwrap().push(ScriptCommand::MouseClick(false));
+        });
+        let queue = commands.clone();
+        engine.register_fn("right_click", move || {
+            queue.lock().unwrap().push(ScriptCommand::MouseClick(true));
+        });
+
+        // handlers (media, navigation, power, etc.):
+        let queue = commands.clone();
+        engine.register_fn("handler", move |name: &str| {
+            queue.lock().unwrap().push(ScriptCommand::Action(Action::Handler { handler: name.to_owned(), args: Value::Null }));
+        });
+        let queue = commands.clone();
+        engine.register_fn("handler", move |name: &str, args: Map| -> ScriptResult<()> {
+            let args: Value = rhai::serde::from_dynamic(&args.into())?;
+            queue.lock().unwrap().push(ScriptCommand::Action(Action::Handler { handler: name.to_owned(), args }));
+            Ok(())
+        });
+        let queue = commands.clone();
+        engine.register_fn("open", move |url: &str| {
+            queue.lock().unwrap().push(ScriptCommand::Action(Action::Open { open: url.to_owned() }));
+        });
+
+        // state:
+        let queue = commands.clone();
+        engine.register_fn("set_mode", move |mode: &str| -> ScriptResult<()> {
+            let mode: Mode = serde_json::from_value(Value::String(mode.to_owned())).map_err(|e| e.to_string())?;
+            queue.lock().unwrap().push(ScriptCommand::SetMode(mode));
+            Ok(())
+        });
+        let queue = commands.clone();
+        engine.register_fn("wait", move |millis: i64| {
+            queue.lock().unwrap().push(ScriptCommand::Wait(millis.max(0) as u64));
+        });
+
+        // key-value store:
+        let kv = store.clone();
+        engine.register_fn("store_get", move |key: &str| -> Dynamic {
+            kv.lock().unwrap().get(key).cloned().unwrap_or(Dynamic::UNIT)
+        });
+        let kv = store.clone();
+        engine.register_fn("store_set", move |key: &str, value: Dynamic| {
+            kv.lock().unwrap().insert(key.to_owned(), value);
+        });
+
+        Self {
+            engine,
+            dir: dir.as_ref().to_path_buf(),
+            commands,
+            deadline,
+            store,
+        }
+    }
+
+    /// Compiles a script by name (from the scripts directory)
+    pub fn compile(&self, name: &str) -> Result<AST> {
+        let path = self.dir.join(name);
+
+        self.engine.compile_file(path)
+            .map_err(|e| Error::ScriptError(name.to_owned(), e.to_string()).into())
+    }
+
+    /// Runs a script with time limit, returns the queued commands
+    pub fn run(&self, name: &str, env: &ScriptEnv, timeout: Duration) -> Result<Vec<ScriptCommand>> {
+        let ast = self.compile(name)?;
+
+        let mut scope = Scope::new();
+        scope.push_constant("mode", env.mode.to_string().to_lowercase());
+        scope.push_constant("volume", env.volume as i64);
+        scope.push_constant("audio_device", env.audio_device.clone());
+        scope.push_constant("repeats", env.repeats as i64);
+
+        self.commands.lock().unwrap().clear();
+        *self.deadline.lock().unwrap() = Some(Instant::now() + timeout);
+
+        let result = self.engine.run_ast_with_scope(&mut scope, &ast);
+
+        *self.deadline.lock().unwrap() = None;
+        let commands = std::mem::take(&mut *self.commands.lock().unwrap());
+
+        result.map_err(|e| Error::ScriptError(name.to_owned(), e.to_string()))?;
+
+        Ok(commands)
+    }
+
+    /// Clears the key-value store
+    pub fn clear_store(&self) {
+        self.store.lock().unwrap().clear();
+    }
+}
+
+/// Parses key by name
+fn parse_key(name: &str) -> ScriptResult<Key> {
+    serde_json::from_value(Value::String(name.to_owned()))
+        .map_err(|_| fmt!("Unknown key '{name}'").into())
+}
+
+/// Parses keys array
+fn parse_keys(keys: Array) -> ScriptResult<Vec<Key>> {
+    keys.into_iter()
+        .map(|key| parse_key(&key.into_string().map_err(|t| fmt!("Expected key name, got {t}"))?))
+        .collect()
+}
@@ -0,0 +1,151 @@
+use crate::prelude::*;
+use std::{ fs, io::{ self, Write, ErrorKind } };
+use std::process::Command;
+
+/// The PID lock file (prevents two instances fighting over the serial ports)
+#[derive(Debug)]
+pub struct PidFile {
+    path: PathBuf,
+}
+
+impl PidFile {
+    /// Creates the lock file (replaces a stale one)
+    pub fn acquire<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        match Self::create(&path) {
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                let pid = fs::read_to_string(&path).ok().and_then(|pid| pid.trim().parse::<u32>().ok());
+
+                if let Some(pid) = pid.filter(|pid| is_running(*pid)) {
+                    return Err(Error::AlreadyRunning(pid).into());
+                }
+
+                info!("Removing stale PID file '{}'..", path.display());
+                fs::remove_file(&path)?;
+
+                Self::create(&path)?;
+            }
+            result => result?,
+        }
+
+        Ok(Self { path })
+    }
+
+    /// Creates a new file with the current process ID
+    fn create(path: &Path) -> io::Result<()> {
+        let mut file = fs::OpenOptions::new().write(true).create_new(true).open(path)?;
+
+        write!(file, "{}", std::process::id())
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Checks the process is running
+fn is_running(pid: u32) -> bool {
+    if cfg!(windows) {
+        Command::new("tasklist")
+            .args(["/FI", &fmt!("PID eq {pid}"), "/NH"])
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+            .unwrap_or(false)
+    } else {
+        Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    }
+}
+
+/// Returns the directory for runtime files (socket, PID file)
+pub fn runtime_dir() -> PathBuf {
+    std::env::var("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::temp_dir())
+}
+
+/// Returns the PID file path (configured or platform default)
+pub fn pid_path(config: &Config) -> PathBuf {
+    match &config.pid_file {
+        Some(path) => PathBuf::from(path),
+        None => runtime_dir().join("avr-pc-remote.pid"),
+    }
+}
+
+/// Waits for a termination signal (SIGTERM/SIGINT or Ctrl-C)
+pub async fn shutdown_signal() -> Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{ signal, SignalKind };
+
+        let mut terminate = signal(SignalKind::terminate())?;
+        let mut interrupt = signal(SignalKind::interrupt())?;
+
+        tokio::select! {
+            _ = terminate.recv() => info!("Received SIGTERM, stopping.."),
+            _ = interrupt.recv() => info!("Received SIGINT, stopping.."),
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        let mut close = tokio::signal::windows::ctrl_close()?;
+
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => {
+                result?;
+                info!("Received Ctrl-C, stopping..");
+            }
+            _ = close.recv() => info!("Console closed, stopping.."),
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates a systemd user unit for the current executable
+pub fn systemd_unit() -> Result<String> {
+    let exe = std::env::current_exe()?;
+    let exe = exe.display();
+
+    Ok(fmt!(
+"[Unit]
+Description={}
+After=graphical-session.target
+PartOf=graphical-session.target
+
+[Service]
+Type=simple
+ExecStart={exe} run
+ExecReload={exe} ctl reload
+KillSignal=SIGTERM
+TimeoutStopSec=5
+Restart=on-failure
+RestartSec=3
+
+[Install]
+WantedBy=graphical-session.target
+", env!("CARGO_PKG_DESCRIPTION")))
+}
+
+/// Writes the systemd user unit (to '~/.config/systemd/user'), returns its path
+pub fn install_systemd_unit() -> Result<PathBuf> {
+    let config_dir = std::env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| std::env::var("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map_err(|_| Error::HomeNotFound)?;
+
+    let dir = config_dir.join("systemd/user");
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join("avr-pc-remote.service");
+    fs::write(&path, systemd_unit()?)?;
+
+    Ok(path)
+}
@@ -0,0 +1,151 @@
+use crate::{ prelude::*, SleepTimer, Media };
+use std::collections::VecDeque;
+
+/// The navigation mode
+#[derive(Debug, Display, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Mode {
+    #[default]
+    Media,
+    Mouse,
+    Selector,
+}
+
+impl Mode {
+    /// Returns the next mode (in cycle)
+    pub fn next(self) -> Self {
+        match self {
+            Self::Media => Self::Mouse,
+            Self::Mouse => Self::Selector,
+            Self::Selector => Self::Media,
+        }
+    }
+}
+
+/// The dispatcher state
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    pub mode: Mode,
+    pub sleep_timer: SleepTimer,
+    pub push_to_talk: Option<Instant>,
+    pub history: History,
+}
+
+/// The reversible state snapshot (active devices, volume, mute and mode)
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Snapshot {
+    pub audio_device: Option<String>,
+    pub micro_device: Option<String>,
+    pub volume: i32,
+    pub audio_muted: bool,
+    pub micro_muted: bool,
+    pub mode: Mode,
+}
+
+impl Snapshot {
+    /// Captures the current state (from the cached media info)
+    pub fn capture(media: &Media, mode: Mode) -> Self {
+        let info = media.get_info();
+        let micro_device = info.devices.iter()
+            .find(|device| device.kind.is_micro_device() && device.is_active)
+            .map(|device| device.name.clone());
+
+        Self {
+            audio_device: info.active.map(|device| device.name),
+            micro_device,
+            volume: info.volume,
+            audio_muted: info.audio_muted,
+            micro_muted: info.micro_muted,
+            mode,
+        }
+    }
+}
+
+/// The undo history (the states before the last bind changes)
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    entries: VecDeque<Snapshot>,
+    undos: u64,
+}
+
+impl History {
+    const LIMIT: usize = 20;
+
+    /// Remembers the state before bind execution (if the bind changed it)
+    /// (repeats are merged with the first press, binds which undid something aren't recorded)
+    pub fn record(&mut self, before: Snapshot, after: &Snapshot, is_repeat: bool, undos: u64) {
+        if undos != self.undos || is_repeat || before == *after { return }
+
+        self.entries.push_back(before);
+        if self.entries.len() > Self::LIMIT {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Returns the taken states counter (to check a bind undid something)
+    pub fn undos(&self) -> u64 {
+        self.undos
+    }
+
+    /// Takes the last remembered state (to restore it)
+    pub fn pop(&mut self) -> Option<Snapshot> {
+        let snapshot = self.entries.pop_back()?;
+        self.undos += 1;
+
+        Some(snapshot)
+    }
+
+    /// Puts back the state which failed to restore
+    pub fn push_back(&mut self, snapshot: Snapshot) {
+        self.entries.push_back(snapshot);
+    }
+
+    /// Returns the remembered states count
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks there is nothing to undo
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(volume: i32) -> Snapshot {
+        Snapshot {
+            audio_device: None,
+            micro_device: None,
+            volume,
+            audio_muted: false,
+            micro_muted: false,
+            mode: Mode::default(),
+        }
+    }
+
+    #[test]
+    fn skips_binds_which_undid() {
+        let mut history = History::default();
+        history.record(snapshot(10), &snapshot(20), false, history.undos());
+
+        let undos = history.undos();
+        let restored = history.pop().unwrap();
+        history.record(snapshot(20), &restored, false, undos);
+
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn undo_outside_bind_doesnt_skip_next_change() {
+        let mut history = History::default();
+        history.record(snapshot(10), &snapshot(20), false, history.undos());
+        history.pop();
+
+        history.record(snapshot(10), &snapshot(30), false, history.undos());
+
+        assert_eq!(history.len(), 1);
+    }
+}
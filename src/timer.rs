@@ -0,0 +1,77 @@
+use crate::prelude::*;
+
+/// The sleep timer state
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TimerState {
+    Idle,
+    Running(Duration),
+}
+
+impl ::std::fmt::Display for TimerState {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        match self {
+            Self::Idle => write!(f, "idle"),
+            Self::Running(remaining) => {
+                let secs = remaining.as_secs();
+                write!(f, "{}:{:02} remaining", secs / 60, secs % 60)
+            }
+        }
+    }
+}
+
+/// The sleep timer
+#[derive(Debug, Clone, Default)]
+pub struct SleepTimer {
+    deadline: Option<Instant>,
+}
+
+impl SleepTimer {
+    /// Creates a new idle timer
+    pub fn new() -> Self {
+        Self { deadline: None }
+    }
+
+    /// Extends the countdown by step (starts it if it's idle), returns new remaining time
+    pub fn extend(&mut self, step: Duration, max: Duration) -> Duration {
+        let remaining = self.remaining().unwrap_or_default();
+        let remaining = (remaining + step).min(max);
+
+        self.deadline = Some(Instant::now() + remaining);
+
+        remaining
+    }
+
+    /// Cancels the countdown, returns true if it was running
+    pub fn cancel(&mut self) -> bool {
+        self.deadline.take().is_some()
+    }
+
+    /// Returns remaining time (if timer is running)
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Checks the timer is running
+    pub fn is_running(&self) -> bool {
+        self.deadline.is_some()
+    }
+
+    /// Returns the timer state
+    pub fn state(&self) -> TimerState {
+        match self.remaining() {
+            Some(remaining) => TimerState::Running(Duration::from_secs(remaining.as_secs())),
+            None => TimerState::Idle,
+        }
+    }
+
+    /// Checks the countdown is expired (the timer becomes idle after that)
+    pub fn poll_expired(&mut self) -> bool {
+        match self.deadline {
+            Some(deadline) if Instant::now() >= deadline => {
+                self.deadline = None;
+                true
+            }
+            _ => false
+        }
+    }
+}
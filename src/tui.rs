@@ -0,0 +1,310 @@
+use crate::{ prelude::*, Media, DispatchEvent, ControlRequest, ControlResponse, ControlMessage };
+use ratatui::{ DefaultTerminal, Frame };
+use ratatui::crossterm::event::{ self, Event, KeyCode, KeyEventKind, KeyModifiers };
+use ratatui::layout::{ Layout, Constraint, Rect };
+use ratatui::style::{ Style, Color, Modifier };
+use ratatui::text::{ Line, Span };
+use ratatui::widgets::{ Block, Borders, List, ListItem, ListState, Paragraph };
+use tokio::sync::{ mpsc, oneshot, broadcast::{ self, error::TryRecvError } };
+use std::collections::VecDeque;
+
+/// The received input (shown in the inputs pane)
+struct InputEntry {
+    time: Instant,
+    source: String,
+    code: String,
+    remote: Option<String>,
+    bind: Option<String>,
+}
+
+/// Restores the terminal and log printing (also if the dashboard is dropped by shutdown)
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        ratatui::restore();
+        LOGGER.set_quiet(false);
+    }
+}
+
+/// The terminal dashboard (live inputs, media state, bind counters and logs)
+pub struct Dashboard {
+    media: Media,
+    events: broadcast::Receiver<DispatchEvent>,
+    logs: broadcast::Receiver<String>,
+    control: mpsc::Sender<ControlMessage>,
+
+    binds: Vec<String>,
+    bind_state: ListState,
+    counters: HashMap<String, (u32, u32)>,
+    inputs: VecDeque<InputEntry>,
+    log_lines: VecDeque<String>,
+    log_scroll: usize,
+    report: Value,
+    pending_report: Option<oneshot::Receiver<ControlResponse>>,
+    start: Instant,
+}
+
+impl Dashboard {
+    const MAX_INPUTS: usize = 100;
+    const MAX_LOGS: usize = 1000;
+    const TICK: Duration = Duration::from_millis(100);
+
+    /// Creates a dashboard (binds are fired through the dispatcher control channel)
+    pub fn new(config: &Config, media: Media, events: broadcast::Receiver<DispatchEvent>, control: mpsc::Sender<ControlMessage>) -> Self {
+        let mut bind_state = ListState::default();
+        bind_state.select(Some(0));
+
+        Self {
+            media,
+            events,
+            logs: LOGGER.subscribe(),
+            control,
+
+            binds: config.bind_names(),
+            bind_state,
+            counters: HashMap::new(),
+            inputs: VecDeque::new(),
+            log_lines: LOGGER.recent(Self::MAX_LOGS).into(),
+            log_scroll: 0,
+            report: Value::Null,
+            pending_report: None,
+            start: Instant::now(),
+        }
+    }
+
+    /// Runs the dashboard until 'q', 'Esc' or 'Ctrl+C' (logs aren't printed meanwhile)
+    pub async fn run(mut self) -> Result<()> {
+        LOGGER.set_quiet(true);
+        let mut terminal = ratatui::init();
+        let _guard = TerminalGuard;
+
+        self.run_loop(&mut terminal).await
+    }
+
+    /// Handles updates and keys, redraws the screen
+    async fn run_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        let mut interval = tokio::time::interval(Self::TICK);
+
+        loop {
+            interval.tick().await;
+
+            self.receive_events();
+            self.receive_logs();
+            self.refresh_report().await;
+
+            // handle pressed keys:
+            while event::poll(Duration::ZERO)? {
+                let Event::Key(key) = event::read()? else { continue };
+                if key.kind != KeyEventKind::Press { continue }
+
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+                    KeyCode::Up => self.bind_state.select_previous(),
+                    KeyCode::Down => self.bind_state.select_next(),
+                    KeyCode::Enter => self.fire_selected().await,
+                    KeyCode::Char('p') => self.toggle_pause().await,
+                    KeyCode::PageUp => self.log_scroll = (self.log_scroll + 10).min(self.log_lines.len()),
+                    KeyCode::PageDown => self.log_scroll = self.log_scroll.saturating_sub(10),
+                    _ => {}
+                }
+            }
+
+            terminal.draw(|frame| self.draw(frame))?;
+        }
+    }
+
+    /// Reads new dispatcher events
+    fn receive_events(&mut self) {
+        loop {
+            match self.events.try_recv() {
+                Ok(DispatchEvent::Input { source, code, remote, bind }) => {
+                    self.inputs.push_front(InputEntry { time: Instant::now(), source, code, remote, bind });
+                    self.inputs.truncate(Self::MAX_INPUTS);
+                }
+                Ok(DispatchEvent::Fired { bind, error, .. }) => {
+                    let (fired, failed) = self.counters.entry(bind).or_default();
+                    *fired += 1;
+                    if error.is_some() { *failed += 1 }
+                }
+                Err(TryRecvError::Lagged(_)) => continue,
+                Err(_) => break,
+            }
+        }
+    }
+
+    /// Reads new log lines
+    fn receive_logs(&mut self) {
+        loop {
+            match self.logs.try_recv() {
+                Ok(line) => {
+                    self.log_lines.push_back(line);
+                    if self.log_lines.len() > Self::MAX_LOGS {
+                        self.log_lines.pop_front();
+                    }
+
+                    // keep the scrolled position:
+                    if self.log_scroll > 0 { self.log_scroll += 1 }
+                }
+                Err(TryRecvError::Lagged(_)) => continue,
+                Err(_) => break,
+            }
+        }
+    }
+
+    /// Requests the dispatcher state (the answer is taken on the next ticks)
+    async fn refresh_report(&mut self) {
+        if let Some(receiver) = &mut self.pending_report {
+            match receiver.try_recv() {
+                Ok(ControlResponse::State { state }) => self.report = state,
+                Err(oneshot::error::TryRecvError::Empty) => return,
+                _ => {}
+            }
+        }
+
+        self.pending_report = self.send(ControlRequest::State).await;
+    }
+
+    /// Sends a control request to the dispatcher
+    async fn send(&self, request: ControlRequest) -> Option<oneshot::Receiver<ControlResponse>> {
+        let (reply, receiver) = oneshot::channel();
+
+        self.control.send(ControlMessage { request, reply }).await.ok()?;
+
+        Some(receiver)
+    }
+
+    /// Sends a control request and logs its error response (in background)
+    async fn send_logged(&self, request: ControlRequest) {
+        let Some(receiver) = self.send(request).await else { return };
+
+        tokio::spawn(async move {
+            if let Ok(ControlResponse::Error { message }) = receiver.await {
+                err!("{message}");
+            }
+        });
+    }
+
+    /// Fires the selected bind
+    async fn fire_selected(&mut self) {
+        let Some(bind) = self.bind_state.selected().and_then(|index| self.binds.get(index)).cloned() else { return };
+
+        self.send_logged(ControlRequest::Fire { bind }).await;
+    }
+
+    /// Pauses/resumes remote input handling
+    async fn toggle_pause(&mut self) {
+        let paused = self.report["paused"].as_bool().unwrap_or(false);
+
+        self.send_logged(if paused { ControlRequest::Resume } else { ControlRequest::Pause }).await;
+    }
+
+    /// Draws the screen
+    fn draw(&mut self, frame: &mut Frame) {
+        let [status_area, middle_area, logs_area, help_area] = Layout::vertical([
+            Constraint::Length(5),
+            Constraint::Min(8),
+            Constraint::Percentage(35),
+            Constraint::Length(1),
+        ]).areas(frame.area());
+
+        let [inputs_area, binds_area] = Layout::horizontal([
+            Constraint::Percentage(60),
+            Constraint::Percentage(40),
+        ]).areas(middle_area);
+
+        self.draw_status(frame, status_area);
+        self.draw_inputs(frame, inputs_area);
+        self.draw_binds(frame, binds_area);
+        self.draw_logs(frame, logs_area);
+
+        let help = "q: quit | Up/Down: select bind | Enter: fire bind | p: pause/resume | PgUp/PgDn: scroll logs";
+        frame.render_widget(Paragraph::new(help).style(Style::default().fg(Color::DarkGray)), help_area);
+    }
+
+    /// Draws mode and media state
+    fn draw_status(&self, frame: &mut Frame, area: Rect) {
+        let info = self.media.get_info();
+        let muted = |muted: bool| if muted { " (muted)" } else { "" };
+        let label = |text: &str| Span::styled(fmt!("{text:<14}"), Style::default().fg(Color::DarkGray));
+
+        let mode = self.report["mode"].as_str().unwrap_or("?").to_owned();
+        let paused = if self.report["paused"].as_bool().unwrap_or(false) { " (paused)" } else { "" };
+        let timer = self.report["sleep_timer"].as_str().unwrap_or("?").to_owned();
+
+        let audio = info.active.as_ref().map(|device| device.name.clone()).unwrap_or(str!("-"));
+        let micro = info.devices.iter()
+            .find(|device| device.kind.is_micro_device() && device.is_active)
+            .map(|device| device.name.clone())
+            .unwrap_or(str!("-"));
+
+        let lines = vec![
+            Line::from(vec![label("Mode"), Span::raw(fmt!("{mode}{paused}")), Span::raw(fmt!("    sleep timer: {timer}"))]),
+            Line::from(vec![label("Audio"), Span::raw(fmt!("{audio} — {}%{}", info.volume, muted(info.audio_muted)))]),
+            Line::from(vec![label("Microphone"), Span::raw(fmt!("{micro}{}", muted(info.micro_muted)))]),
+        ];
+
+        frame.render_widget(Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" State ")), area);
+    }
+
+    /// Draws the last received inputs
+    fn draw_inputs(&self, frame: &mut Frame, area: Rect) {
+        let items = self.inputs.iter()
+            .map(|input| {
+                let secs = input.time.duration_since(self.start).as_secs();
+                let resolved = match (&input.remote, &input.bind) {
+                    (Some(remote), Some(bind)) => Span::styled(fmt!("{remote} → {bind}"), Style::default().fg(Color::Green)),
+                    (Some(remote), None) => Span::styled(fmt!("{remote} → (unbound)"), Style::default().fg(Color::Yellow)),
+                    (None, _) => Span::styled("(ignored)", Style::default().fg(Color::DarkGray)),
+                };
+
+                ListItem::new(Line::from(vec![
+                    Span::styled(fmt!("{:02}:{:02}:{:02} ", secs / 3600, secs / 60 % 60, secs % 60), Style::default().fg(Color::DarkGray)),
+                    Span::raw(fmt!("[{}] {} ", input.source, input.code)),
+                    resolved,
+                ]))
+            })
+            .collect::<Vec<_>>();
+
+        frame.render_widget(List::new(items).block(Block::default().borders(Borders::ALL).title(" Inputs ")), area);
+    }
+
+    /// Draws binds with their counters
+    fn draw_binds(&mut self, frame: &mut Frame, area: Rect) {
+        let items = self.binds.iter()
+            .map(|name| {
+                let text = match self.counters.get(name) {
+                    Some((fired, 0)) => fmt!("{name} ({fired})"),
+                    Some((fired, failed)) => fmt!("{name} ({fired}, {failed} failed)"),
+                    None => name.clone(),
+                };
+
+                ListItem::new(text)
+            })
+            .collect::<Vec<_>>();
+
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(" Binds "))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        frame.render_stateful_widget(list, area, &mut self.bind_state);
+    }
+
+    /// Draws the log pane (follows new lines unless it's scrolled up)
+    fn draw_logs(&self, frame: &mut Frame, area: Rect) {
+        let height = area.height.saturating_sub(2) as usize;
+        let end = self.log_lines.len().saturating_sub(self.log_scroll);
+        let start = end.saturating_sub(height);
+
+        let lines = self.log_lines.range(start..end)
+            .map(|line| {
+                let style = if line.starts_with("[ERROR]") { Style::default().fg(Color::Red) } else { Style::default() };
+                Line::styled(line.clone(), style)
+            })
+            .collect::<Vec<_>>();
+
+        let title = if self.log_scroll > 0 { fmt!(" Logs (+{}) ", self.log_scroll) } else { str!(" Logs ") };
+        frame.render_widget(Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title)), area);
+    }
+}
@@ -0,0 +1,332 @@
+use crate::{ prelude::*, Bind, Code, Registry, Dispatcher, DispatchEvent, ControlRequest, ControlResponse, ControlMessage, key_names, binds::DEFAULT_REMOTE };
+use axum::{ Router, Json, routing::get, extract::{ Path as UrlPath, Query, State, Request }, middleware::{ self, Next }, response::{ Html, IntoResponse, Response }, http::{ StatusCode, Method, header } };
+use tokio::sync::{ mpsc, oneshot, broadcast };
+use tokio::task::JoinHandle;
+use std::net::{ SocketAddr, IpAddr };
+
+/// The web page
+const INDEX_HTML: &str = include_str!("index.html");
+
+/// The web UI shared state
+#[derive(Clone)]
+struct WebState {
+    registry: Registry,
+    events: broadcast::Sender<DispatchEvent>,
+    control: mpsc::Sender<ControlMessage>,
+    editing: Arc<Mutex<()>>,
+}
+
+/// The API error (JSON response)
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(e: Error) -> Self {
+        Self(StatusCode::BAD_REQUEST, e.to_string())
+    }
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for ApiError {
+    fn from(e: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        Self(StatusCode::BAD_REQUEST, e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ApiError {
+    fn from(e: serde_json::Error) -> Self {
+        Self(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+    }
+}
+
+type ApiResult = StdResult<Json<Value>, ApiError>;
+
+/// The capture request query
+#[derive(Debug, Deserialize)]
+struct CaptureQuery {
+    #[serde(default = "CaptureQuery::default_timeout")]
+    timeout_ms: u64,
+}
+
+impl CaptureQuery {
+    fn default_timeout() -> u64 { 15000 }
+}
+
+/// The local web UI for binds editing (served on loopback address only)
+pub struct WebUi {
+    addr: SocketAddr,
+    state: WebState,
+}
+
+impl WebUi {
+    /// Creates a web UI (checks the address is loopback)
+    pub fn new(addr: &str, registry: Registry, events: broadcast::Sender<DispatchEvent>, control: mpsc::Sender<ControlMessage>) -> Result<Self> {
+        let addr: SocketAddr = addr.parse().map_err(|_| Error::WebUiNotLocal(addr.to_owned()))?;
+        if !addr.ip().is_loopback() {
+            return Err(Error::WebUiNotLocal(addr.to_string()).into());
+        }
+
+        Ok(Self {
+            addr,
+            state: WebState { registry, events, control, editing: Arc::new(Mutex::new(())) },
+        })
+    }
+
+    /// Starts serving in background
+    pub fn spawn(self) -> JoinHandle<()> {
+        let router = Router::new()
+            .route("/", get(index))
+            .route("/api/meta", get(meta))
+            .route("/api/binds", get(list_binds))
+            .route("/api/binds/{remote}/{code}", axum::routing::put(save_bind).delete(delete_bind))
+            .route("/api/capture", get(capture))
+            .with_state(self.state)
+            .layer(middleware::from_fn_with_state(self.addr, check_host));
+
+        let addr = self.addr;
+
+        tokio::spawn(async move {
+            let listener = match tokio::net::TcpListener::bind(addr).await {
+                Ok(listener) => listener,
+                Err(e) => return err!("Error with starting web UI on '{addr}': {e}"),
+            };
+            info!("Web UI is available at http://{addr}");
+
+            if let Err(e) = axum::serve(listener, router).await {
+                err!("Web UI error: {e}");
+            }
+        })
+    }
+}
+
+/// Returns the web page
+async fn index() -> Html<&'static str> {
+    Html(INDEX_HTML)
+}
+
+/// Returns the key names, handler names and remotes (for dropdowns)
+async fn meta(State(state): State<WebState>) -> ApiResult {
+    let cfg = CONFIG.lock().await;
+
+    let mut remotes = vec![str!(DEFAULT_REMOTE)];
+    let mut names = cfg.remotes.keys().cloned().collect::<Vec<_>>();
+    names.sort();
+    remotes.extend(names);
+
+    Ok(Json(serde_json::json!({
+        "keys": key_names(),
+        "handlers": state.registry.names(),
+        "remotes": remotes,
+    })))
+}
+
+/// Returns binds by remotes
+async fn list_binds() -> ApiResult {
+    let cfg = CONFIG.lock().await;
+
+    let mut remotes = serde_json::Map::new();
+    remotes.insert(str!(DEFAULT_REMOTE), serde_json::to_value(&cfg.binds)?);
+    for (name, remote) in &cfg.remotes {
+        remotes.insert(name.clone(), serde_json::to_value(&remote.binds)?);
+    }
+
+    Ok(Json(Value::Object(remotes)))
+}
+
+/// Adds or replaces a bind (checks its handler and script), saves config and reloads the dispatcher
+async fn save_bind(State(state): State<WebState>, UrlPath((remote, code)): UrlPath<(String, String)>, Json(bind): Json<Bind>) -> ApiResult {
+    let code = Code::parse(&code).ok_or_else(|| Error::InvalidBind(code.clone(), str!("invalid code")))?;
+    state.registry.validate(&bind.action)?;
+
+    update_binds(&state, &remote, |binds| {
+        // replace the code written in another case:
+        binds.retain(|key, _| !key.eq_ignore_ascii_case(&code.value));
+        binds.insert(code.value.clone(), bind);
+
+        Ok(())
+    }).await?;
+    info!("Saved bind '{}' of '{remote}' remote by web UI", code.value);
+
+    Ok(Json(serde_json::json!({ "code": code.value })))
+}
+
+/// Removes a bind, saves config and reloads the dispatcher
+async fn delete_bind(State(state): State<WebState>, UrlPath((remote, code)): UrlPath<(String, String)>) -> ApiResult {
+    update_binds(&state, &remote, |binds| {
+        let count = binds.len();
+        binds.retain(|key, _| !key.eq_ignore_ascii_case(&code));
+
+        if binds.len() == count {
+            return Err(Error::BindNotFound(code.clone()).into());
+        }
+        Ok(())
+    }).await?;
+    info!("Removed bind '{code}' of '{remote}' remote by web UI");
+
+    Ok(Json(Value::Null))
+}
+
+/// Changes remote binds in a config copy, checks and saves it, reloads the dispatcher (the file is restored if reload failed)
+async fn update_binds<F>(state: &WebState, remote: &str, change: F) -> StdResult<(), ApiError>
+where
+    F: FnOnce(&mut HashMap<String, Bind>) -> Result<()>,
+{
+    let _editing = state.editing.lock().await;
+
+    let mut previous = CONFIG.lock().await.clone();
+    let mut config = previous.clone();
+
+    let binds = config.remote_binds_mut(remote).ok_or_else(|| Error::RemoteNotFound(remote.to_owned()))?;
+    change(binds)?;
+
+    // check the changed config before it's written:
+    Dispatcher::check_config(&state.registry, &config)?;
+    config.save()?;
+
+    if let Err(e) = reload(state).await {
+        previous.save()?;
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Waits for the next remote code (from the live input stream)
+async fn capture(State(state): State<WebState>, Query(query): Query<CaptureQuery>) -> ApiResult {
+    let mut events = state.events.subscribe();
+
+    let wait = async {
+        loop {
+            match events.recv().await {
+                Ok(DispatchEvent::Input { code, remote, bind, .. }) => return Ok((code, remote, bind)),
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return Err(Error::DispatcherStopped),
+            }
+        }
+    };
+
+    let (code, remote, bind) = tokio::time::timeout(Duration::from_millis(query.timeout_ms), wait).await
+        .map_err(|_| ApiError(StatusCode::REQUEST_TIMEOUT, str!("no remote button was pressed")))??;
+
+    let code = Code::parse(&code).ok_or_else(|| Error::InvalidBind(code.clone(), str!("invalid code")))?;
+
+    Ok(Json(serde_json::json!({
+        "code": code.value,
+        "protocol": code.protocol,
+        "remote": remote,
+        "bind": bind,
+    })))
+}
+
+/// Reloads the dispatcher config (from the saved file)
+async fn reload(state: &WebState) -> StdResult<(), ApiError> {
+    let (reply, receiver) = oneshot::channel();
+
+    state.control.send(ControlMessage { request: ControlRequest::Reload, reply }).await
+        .map_err(|_| Error::DispatcherStopped)?;
+
+    match receiver.await.map_err(|_| Error::DispatcherStopped)? {
+        ControlResponse::Error { message } => Err(ApiError(StatusCode::INTERNAL_SERVER_ERROR, message)),
+        _ => Ok(()),
+    }
+}
+
+/// Rejects requests to another host (DNS rebinding) and cross-origin changes
+async fn check_host(State(addr): State<SocketAddr>, request: Request, next: Next) -> Response {
+    if let Err(e) = check_headers(&request, &addr) {
+        return e.into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Checks the request 'Host' and 'Origin' headers
+fn check_headers(request: &Request, addr: &SocketAddr) -> StdResult<(), ApiError> {
+    let header = |name| request.headers().get(name).and_then(|value| value.to_str().ok());
+
+    if !header(header::HOST).is_some_and(|host| is_local_host(host, addr)) {
+        return Err(ApiError(StatusCode::FORBIDDEN, str!("unknown host")));
+    }
+
+    // browsers send 'Origin' with cross-origin requests:
+    let cross_origin = header(header::ORIGIN)
+        .is_some_and(|origin| !origin.strip_prefix("http://").is_some_and(|host| is_local_host(host, addr)));
+
+    if cross_origin && matches!(*request.method(), Method::PUT | Method::DELETE) {
+        return Err(ApiError(StatusCode::FORBIDDEN, str!("cross-origin request")));
+    }
+
+    Ok(())
+}
+
+/// Checks the host ('host:port') is the web UI address or 'localhost' on its port
+fn is_local_host(host: &str, addr: &SocketAddr) -> bool {
+    let (name, port) = host.rsplit_once(':')
+        .and_then(|(name, port)| Some((name, port.parse::<u16>().ok()?)))
+        .unwrap_or((host, 80));
+
+    let ip = match addr.ip() {
+        IpAddr::V6(ip) => fmt!("[{ip}]"),
+        ip => ip.to_string(),
+    };
+
+    port == addr.port() && (name.eq_ignore_ascii_case("localhost") || name == ip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+
+    fn request(method: &str, host: &str, origin: Option<&str>) -> Request {
+        let mut builder = Request::builder().method(method).uri("/api/binds").header(header::HOST, host);
+        if let Some(origin) = origin {
+            builder = builder.header(header::ORIGIN, origin);
+        }
+
+        builder.body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn allows_local_hosts() {
+        let addr: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+
+        assert!(is_local_host("127.0.0.1:8080", &addr));
+        assert!(is_local_host("localhost:8080", &addr));
+        assert!(is_local_host("LocalHost:8080", &addr));
+
+        assert!(!is_local_host("127.0.0.1:9090", &addr));
+        assert!(!is_local_host("evil.com:8080", &addr));
+        assert!(!is_local_host("localhost", &addr));
+
+        let addr: SocketAddr = "[::1]:80".parse().unwrap();
+
+        assert!(is_local_host("[::1]", &addr));
+        assert!(is_local_host("[::1]:80", &addr));
+        assert!(is_local_host("localhost", &addr));
+    }
+
+    #[test]
+    fn rejects_foreign_host() {
+        let addr: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+
+        assert!(check_headers(&request("GET", "localhost:8080", None), &addr).is_ok());
+        assert!(check_headers(&request("GET", "rebind.example:8080", None), &addr).is_err());
+    }
+
+    #[test]
+    fn rejects_cross_origin_changes() {
+        let addr: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+
+        assert!(check_headers(&request("PUT", "127.0.0.1:8080", Some("http://127.0.0.1:8080")), &addr).is_ok());
+        assert!(check_headers(&request("DELETE", "127.0.0.1:8080", None), &addr).is_ok());
+
+        assert!(check_headers(&request("PUT", "127.0.0.1:8080", Some("http://evil.com")), &addr).is_err());
+        assert!(check_headers(&request("DELETE", "127.0.0.1:8080", Some("null")), &addr).is_err());
+        assert!(check_headers(&request("GET", "127.0.0.1:8080", Some("http://evil.com")), &addr).is_ok());
+    }
+}